@@ -0,0 +1,177 @@
+use crate::board::Pos;
+
+// The moves allowed from one cell to the next, as (row, col) offsets.
+// With wrap set the board is a torus: moves off one edge come back on the opposite edge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Adjacency {
+    offsets: Vec<(isize, isize)>,
+    wrap: bool,
+}
+
+impl Adjacency {
+    // Standard Boggle: all 8 surrounding cells
+    pub fn standard() -> Adjacency {
+        Adjacency {
+            offsets: vec![
+                (-1, -1),
+                (0, -1),
+                (-1, 1),
+                (0, 1),
+                (-1, 0),
+                (1, -1),
+                (1, 1),
+                (1, 0),
+            ],
+            wrap: false,
+        }
+    }
+
+    // Only up, down, left and right
+    pub fn orthogonal() -> Adjacency {
+        Adjacency {
+            offsets: vec![(-1, 0), (0, -1), (0, 1), (1, 0)],
+            wrap: false,
+        }
+    }
+
+    // Any set of moves, e.g. knight moves for chess Boggle.
+    // Staying in place and repeated offsets are dropped.
+    pub fn custom(offsets: &[(isize, isize)]) -> Adjacency {
+        let mut unique = Vec::with_capacity(offsets.len());
+        for &offset in offsets {
+            if offset != (0, 0) && !unique.contains(&offset) {
+                unique.push(offset);
+            }
+        }
+        Adjacency {
+            offsets: unique,
+            wrap: false,
+        }
+    }
+
+    // The same moves on a toroidal board
+    pub fn wrapping(self) -> Adjacency {
+        Adjacency { wrap: true, ..self }
+    }
+
+    // get the neighbors of this pos on a board of the given size
+    pub fn neighbors(&self, width: usize, height: usize, pos: Pos) -> Vec<Pos> {
+        if self.wrap {
+            return self.wrapped_neighbors(width, height, pos);
+        }
+        self.offsets
+            .iter()
+            .filter_map(|&(d_row, d_col)| {
+                let row = pos.0.checked_add_signed(d_row)?;
+                let col = pos.1.checked_add_signed(d_col)?;
+                if row < height && col < width {
+                    Some((row, col))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    // On small boards several offsets can wrap onto the same cell, or back onto pos itself,
+    // so those are skipped to keep each neighbor listed once
+    fn wrapped_neighbors(&self, width: usize, height: usize, pos: Pos) -> Vec<Pos> {
+        let mut v = Vec::with_capacity(self.offsets.len());
+        for &(d_row, d_col) in &self.offsets {
+            let row = (pos.0 as isize + d_row).rem_euclid(height as isize) as usize;
+            let col = (pos.1 as isize + d_col).rem_euclid(width as isize) as usize;
+            if (row, col) != pos && !v.contains(&(row, col)) {
+                v.push((row, col));
+            }
+        }
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut v: Vec<Pos>) -> Vec<Pos> {
+        v.sort();
+        v
+    }
+
+    #[test]
+    fn standard() {
+        let adj = Adjacency::standard();
+        assert_eq!(sorted(adj.neighbors(3, 3, (0, 0))), vec![(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(
+            sorted(adj.neighbors(3, 3, (0, 1))),
+            vec![(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)]
+        );
+        assert_eq!(sorted(adj.neighbors(3, 3, (2, 2))), vec![(1, 1), (1, 2), (2, 1)]);
+        assert_eq!(adj.neighbors(3, 3, (1, 1)).len(), 8);
+        assert_eq!(adj.neighbors(1, 1, (0, 0)), vec![]);
+    }
+
+    #[test]
+    fn orthogonal() {
+        let adj = Adjacency::orthogonal();
+        assert_eq!(sorted(adj.neighbors(3, 3, (0, 0))), vec![(0, 1), (1, 0)]);
+        assert_eq!(sorted(adj.neighbors(3, 3, (0, 1))), vec![(0, 0), (0, 2), (1, 1)]);
+        assert_eq!(sorted(adj.neighbors(3, 3, (2, 2))), vec![(1, 2), (2, 1)]);
+        assert_eq!(adj.neighbors(3, 3, (1, 1)).len(), 4);
+    }
+
+    #[test]
+    fn wrapping() {
+        let adj = Adjacency::standard().wrapping();
+        assert_eq!(
+            sorted(adj.neighbors(4, 4, (0, 0))),
+            vec![(0, 1), (0, 3), (1, 0), (1, 1), (1, 3), (3, 0), (3, 1), (3, 3)]
+        );
+        assert_eq!(
+            sorted(adj.neighbors(4, 4, (3, 1))),
+            vec![(0, 0), (0, 1), (0, 2), (2, 0), (2, 1), (2, 2), (3, 0), (3, 2)]
+        );
+        let orth = Adjacency::orthogonal().wrapping();
+        assert_eq!(
+            sorted(orth.neighbors(3, 3, (0, 2))),
+            vec![(0, 0), (0, 1), (1, 2), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn wrapping_tiny_boards() {
+        let adj = Adjacency::standard().wrapping();
+        assert_eq!(adj.neighbors(1, 1, (0, 0)), vec![]);
+        assert_eq!(sorted(adj.neighbors(2, 1, (0, 0))), vec![(0, 1)]);
+        assert_eq!(
+            sorted(adj.neighbors(2, 2, (1, 1))),
+            vec![(0, 0), (0, 1), (1, 0)]
+        );
+        assert_eq!(
+            sorted(adj.neighbors(3, 2, (0, 1))),
+            vec![(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)]
+        );
+        let knight = Adjacency::custom(&[(1, 2), (2, 1)]).wrapping();
+        assert_eq!(sorted(knight.neighbors(2, 2, (0, 0))), vec![(0, 1), (1, 0)]);
+        assert_eq!(knight.neighbors(1, 2, (1, 0)), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn custom() {
+        let knight = Adjacency::custom(&[
+            (-2, -1),
+            (-2, 1),
+            (-1, -2),
+            (-1, 2),
+            (1, -2),
+            (1, 2),
+            (2, -1),
+            (2, 1),
+            (2, 1),
+            (0, 0),
+        ]);
+        assert_eq!(sorted(knight.neighbors(3, 3, (0, 0))), vec![(1, 2), (2, 1)]);
+        assert_eq!(sorted(knight.neighbors(4, 3, (0, 1))), vec![(1, 3), (2, 0), (2, 2)]);
+        assert_eq!(knight.neighbors(3, 3, (1, 1)), vec![]);
+        assert_eq!(knight.neighbors(5, 5, (2, 2)).len(), 8);
+    }
+}
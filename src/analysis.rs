@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::board::Tile;
+use crate::error::Error;
+use crate::grid::Grid;
+use crate::scoring::ScoringRule;
+use crate::solver::find_all_and_used_cells;
+use crate::word_dict::Trie;
+
+// Words seen less often than this many times per million words count as rare
+pub const RARE_FREQUENCY: u32 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub word_count: usize,
+    pub total_score: u32,
+    // Word length in letters -> number of words that long
+    pub length_histogram: BTreeMap<usize, usize>,
+    // Ties go to the alphabetically first word
+    pub longest_word: Option<String>,
+    // Share of words below RARE_FREQUENCY, or None if analyze had no word frequencies
+    pub rare_fraction: Option<f64>,
+    // Non-blank cells that sit on no path of any word
+    pub dead_cells: usize,
+    // Non-blank cells on the board
+    pub live_cells: usize,
+}
+
+impl Report {
+    // Graded on words per playable cell: boards full of words are easy to get going on,
+    // sparse ones are hard. A high share of rare words bumps the grade up one step.
+    pub fn difficulty(&self) -> Difficulty {
+        let density = self.word_count as f64 / self.live_cells.max(1) as f64;
+        let grade = if density >= 4.0 {
+            Difficulty::Easy
+        } else if density >= 1.5 {
+            Difficulty::Medium
+        } else {
+            Difficulty::Hard
+        };
+        let mostly_rare = self.rare_fraction.is_some_and(|f| f > 0.5);
+        match grade {
+            Difficulty::Easy if mostly_rare => Difficulty::Medium,
+            Difficulty::Medium if mostly_rare => Difficulty::Hard,
+            grade => grade,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "words:      {}", self.word_count)?;
+        writeln!(f, "score:      {}", self.total_score)?;
+        match &self.longest_word {
+            Some(word) => writeln!(f, "longest:    {} ({})", word, word.chars().count())?,
+            None => writeln!(f, "longest:    -")?,
+        }
+        match self.rare_fraction {
+            Some(fraction) => writeln!(f, "rare words: {:.0}%", fraction * 100.0)?,
+            None => writeln!(f, "rare words: -")?,
+        }
+        writeln!(f, "dead cells: {} of {}", self.dead_cells, self.live_cells)?;
+        writeln!(f, "difficulty: {}", self.difficulty())?;
+        writeln!(f, "lengths:")?;
+        for (len, count) in &self.length_histogram {
+            writeln!(
+                f,
+                "  {:>2} | {:<5} {}",
+                len,
+                count,
+                "#".repeat((*count).min(50))
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// frequency is the same kind of word frequency source as ranking::Ranking::Rarity takes,
+// in occurrences per million words. Without one there's no telling which words are rare.
+pub fn analyze<G: Grid>(
+    words: &impl Trie,
+    grid: &G,
+    rule: &impl ScoringRule<G::Pos>,
+    frequency: Option<&dyn Fn(&str) -> u32>,
+) -> Result<Report, Error> {
+    let (found, used) = find_all_and_used_cells(words, grid)?;
+    let mut length_histogram = BTreeMap::new();
+    let mut longest_word: Option<&str> = None;
+    let mut rare = 0;
+    let mut total_score = 0;
+    for (word, path) in &found {
+        let len = word.chars().count();
+        *length_histogram.entry(len).or_insert(0) += 1;
+        let longer = match longest_word {
+            None => true,
+            Some(best) => {
+                (len, std::cmp::Reverse(word.as_str()))
+                    > (best.chars().count(), std::cmp::Reverse(best))
+            }
+        };
+        if longer {
+            longest_word = Some(word);
+        }
+        if frequency.is_some_and(|frequency| frequency(word) < RARE_FREQUENCY) {
+            rare += 1;
+        }
+        total_score += rule.score(word, path);
+    }
+    // Blank cells are never on a path, so every used cell is live
+    let live_cells = grid
+        .positions()
+        .into_iter()
+        .filter(|pos| !matches!(grid.tile(*pos), Tile::Blank))
+        .count();
+    Ok(Report {
+        word_count: found.len(),
+        total_score,
+        length_histogram,
+        longest_word: longest_word.map(str::to_string),
+        rare_fraction: frequency.map(|_| {
+            if found.is_empty() {
+                0.0
+            } else {
+                rare as f64 / found.len() as f64
+            }
+        }),
+        dead_cells: live_cells - used.len(),
+        live_cells,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::board::Board;
+    use crate::scoring::Boggle;
+    use crate::word_dict::hashmap::TrieHashMap;
+
+    #[test]
+    fn report() {
+        let words = TrieHashMap::from_words(&["hello", "hell", "yo", "xhe", "jazz", "zzz"]);
+        let board = Board::parse("xyq\nh o\nell").unwrap();
+        let frequencies = HashMap::from([("hello", 120), ("hell", 40), ("yo", 3)]);
+        let frequency = |word: &str| frequencies.get(word).copied().unwrap_or(0);
+        let report = analyze(&words, &board, &Boggle, Some(&frequency)).unwrap();
+        assert_eq!(report.word_count, 4);
+        assert_eq!(report.total_score, 2 + 1 + 1);
+        assert_eq!(
+            report.length_histogram,
+            BTreeMap::from([(2, 1), (3, 1), (4, 1), (5, 1)])
+        );
+        assert_eq!(report.longest_word.as_deref(), Some("hello"));
+        // xhe is the only rare word
+        assert_eq!(report.rare_fraction, Some(0.25));
+        // Only q is unused; the blank doesn't count
+        assert_eq!(report.live_cells, 8);
+        assert_eq!(report.dead_cells, 1);
+        assert_eq!(report.difficulty(), Difficulty::Hard);
+        let text = report.to_string();
+        assert!(text.contains("longest:    hello (5)"));
+        assert!(text.contains("rare words: 25%"));
+        assert!(text.contains("difficulty: hard"));
+        let report = analyze(&words, &board, &Boggle, None).unwrap();
+        assert_eq!(report.rare_fraction, None);
+        assert!(report.to_string().contains("rare words: -"));
+    }
+
+    #[test]
+    fn longest_ties_alphabetical() {
+        let words = TrieHashMap::from_words(&["tea", "eat", "ate"]);
+        let report = analyze(&words, &Board::parse("te\nat").unwrap(), &Boggle, None).unwrap();
+        assert_eq!(report.longest_word.as_deref(), Some("ate"));
+    }
+
+    #[test]
+    fn dead_cells_on_any_path() {
+        // x is dead, but the bottom right a is only on the second path for "ab"
+        let words = TrieHashMap::from_words(&["ab"]);
+        let report = analyze(&words, &Board::parse("ab\nxa").unwrap(), &Boggle, None).unwrap();
+        assert_eq!(report.dead_cells, 1);
+    }
+
+    #[test]
+    fn empty_board() {
+        let words = TrieHashMap::from_words(&["cat"]);
+        let board = Board::parse("zz\nzz").unwrap();
+        let report = analyze(&words, &board, &Boggle, Some(&|_| 0)).unwrap();
+        assert_eq!(report.word_count, 0);
+        assert_eq!(report.longest_word, None);
+        assert_eq!(report.rare_fraction, Some(0.0));
+        assert_eq!(report.dead_cells, 4);
+        assert_eq!(report.difficulty(), Difficulty::Hard);
+    }
+
+    #[test]
+    fn grades() {
+        let mut report = Report {
+            word_count: 80,
+            total_score: 100,
+            length_histogram: BTreeMap::new(),
+            longest_word: None,
+            rare_fraction: Some(0.1),
+            dead_cells: 0,
+            live_cells: 16,
+        };
+        assert_eq!(report.difficulty(), Difficulty::Easy);
+        report.rare_fraction = Some(0.6);
+        assert_eq!(report.difficulty(), Difficulty::Medium);
+        report.word_count = 30;
+        assert_eq!(report.difficulty(), Difficulty::Hard);
+        report.rare_fraction = None;
+        assert_eq!(report.difficulty(), Difficulty::Medium);
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+use crate::board::{Board, BoardError};
+use crate::error::Error;
+
+// One board's text from a batch
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchBoard {
+    // From the board's "# id" header, or its position in the batch counting from 1
+    pub id: String,
+    // Line of the input the board's first row is on, counting from 1
+    pub line: usize,
+    pub text: String,
+}
+
+impl BatchBoard {
+    // Parse the board, with errors reported against lines of the whole input
+    pub fn parse(&self) -> Result<Board, BoardError> {
+        Board::parse(&self.text).map_err(|e| match e.line() {
+            Some(line) => e.on_line(line + self.line - 1),
+            None => e,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct BatchResult<S> {
+    pub id: String,
+    pub outcome: Result<S, Error>,
+}
+
+// Split input into boards. Boards are separated by blank lines and may start with a
+// "# id" header line; a '#' tile at the start of a first row must not be followed by a space.
+// Boards are read lazily, so input can be streamed.
+pub fn read_boards<'a, R: BufRead + 'a>(
+    input: R,
+    name: &'a str,
+) -> impl Iterator<Item = Result<BatchBoard, Error>> + 'a {
+    let mut lines = input.lines().enumerate();
+    let mut count = 0;
+    std::iter::from_fn(move || {
+        let mut board: Option<BatchBoard> = None;
+        for (i, line) in lines.by_ref() {
+            let line = match line {
+                Ok(line) => line,
+                Err(source) => {
+                    return Some(Err(Error::Io {
+                        path: name.to_string(),
+                        source,
+                    }))
+                }
+            };
+            if line.trim().is_empty() {
+                if board.is_some() {
+                    break;
+                }
+                continue;
+            }
+            match &mut board {
+                Some(board) => {
+                    board.text.push_str(&line);
+                    board.text.push('\n');
+                }
+                None => {
+                    count += 1;
+                    let (id, text) = match line.strip_prefix("# ") {
+                        Some(id) => (id.trim().to_string(), String::new()),
+                        None => (count.to_string(), format!("{}\n", line)),
+                    };
+                    board = Some(BatchBoard {
+                        id,
+                        line: if text.is_empty() { i + 2 } else { i + 1 },
+                        text,
+                    });
+                }
+            }
+        }
+        board.map(Ok)
+    })
+}
+
+// Solve every board in the input on a pool of threads, calling emit with each result in
+// input order as soon as it and every board before it are done. emit runs on its own thread,
+// so results go out while the input is still waiting on the next board. A board that doesn't
+// parse or fails to solve is emitted as an error and the batch carries on; only failing to
+// read the input stops it. The dictionary is shared by capturing it in solve.
+pub fn solve_batch<R, S, F>(
+    input: R,
+    name: &str,
+    threads: usize,
+    solve: F,
+    mut emit: impl FnMut(BatchResult<S>) + Send,
+) -> Result<(), Error>
+where
+    R: BufRead,
+    S: Send,
+    F: Fn(&Board) -> Result<S, Error> + Sync,
+{
+    let threads = threads.max(1);
+    // Bounded so a huge input isn't read far ahead of the solvers
+    let (job_tx, job_rx) = mpsc::sync_channel::<(usize, BatchBoard)>(threads * 2);
+    let job_rx = Mutex::new(job_rx);
+    let (done_tx, done_rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let (job_rx, done_tx, solve) = (&job_rx, done_tx.clone(), &solve);
+            scope.spawn(move || loop {
+                let job = job_rx.lock().expect("batch worker panicked").recv();
+                let Ok((index, board)) = job else {
+                    break;
+                };
+                let outcome = board.parse().map_err(Error::from).and_then(|b| solve(&b));
+                let result = BatchResult {
+                    id: board.id,
+                    outcome,
+                };
+                if done_tx.send((index, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(done_tx);
+
+        // Results can finish out of order; hold them until everything before is emitted
+        scope.spawn(move || {
+            let mut pending = BTreeMap::new();
+            let mut next = 0;
+            for (index, result) in done_rx {
+                pending.insert(index, result);
+                while let Some(result) = pending.remove(&next) {
+                    emit(result);
+                    next += 1;
+                }
+            }
+        });
+
+        let mut read_error = None;
+        for (index, board) in read_boards(input, name).enumerate() {
+            match board {
+                Ok(board) => job_tx.send((index, board)).expect("batch workers exited"),
+                Err(e) => {
+                    read_error = Some(e);
+                    break;
+                }
+            }
+        }
+        drop(job_tx);
+        read_error.map_or(Ok(()), Err)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, BufReader, Read};
+    use std::sync::Condvar;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::solver::find_all;
+    use crate::word_dict::hashmap::TrieHashMap;
+
+    const BATCH: &str = "# first\r\nhe\r\nyo\r\n\r\n\r\nxyq\nh o\nell\n\n# ragged\nab\nc\n\nol\n";
+
+    #[test]
+    fn split_boards() {
+        let boards: Vec<BatchBoard> = read_boards(BATCH.as_bytes(), "batch")
+            .map(Result::unwrap)
+            .collect();
+        let ids: Vec<(&str, usize)> = boards.iter().map(|b| (b.id.as_str(), b.line)).collect();
+        assert_eq!(ids, vec![("first", 2), ("2", 6), ("ragged", 11), ("4", 14)]);
+        assert_eq!(boards[0].text, "he\nyo\n");
+        assert_eq!(boards[1].text, "xyq\nh o\nell\n");
+        assert_eq!(
+            boards[2].parse(),
+            Err(BoardError::Ragged {
+                line: 12,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(read_boards("\n\n".as_bytes(), "batch").count(), 0);
+    }
+
+    #[test]
+    fn batch_keeps_going() {
+        let words = TrieHashMap::from_words(&["he", "hell", "hello", "lo", "ol", "yo"]);
+        for threads in [1, 2, 8] {
+            let mut results = Vec::new();
+            solve_batch(
+                BATCH.as_bytes(),
+                "batch",
+                threads,
+                |board| Ok(find_all(&words, board)?.len()),
+                |result| results.push((result.id, result.outcome.ok())),
+            )
+            .unwrap();
+            assert_eq!(
+                results,
+                vec![
+                    ("first".to_string(), Some(2)),
+                    ("2".to_string(), Some(6)),
+                    ("ragged".to_string(), None),
+                    ("4".to_string(), Some(2)),
+                ]
+            );
+        }
+    }
+
+    // Hands out one board per read, and only once every board before it has been emitted
+    struct SlowInput<'a> {
+        boards: &'a [&'a str],
+        next: usize,
+        emitted: &'a (Mutex<usize>, Condvar),
+    }
+
+    impl Read for SlowInput<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let Some(board) = self.boards.get(self.next) else {
+                return Ok(0);
+            };
+            let (count, changed) = self.emitted;
+            let (_count, wait) = changed
+                .wait_timeout_while(count.lock().unwrap(), Duration::from_secs(5), |n| {
+                    *n < self.next
+                })
+                .unwrap();
+            if wait.timed_out() {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "a solved board was held back until more input arrived",
+                ));
+            }
+            buf[..board.len()].copy_from_slice(board.as_bytes());
+            self.next += 1;
+            Ok(board.len())
+        }
+    }
+
+    #[test]
+    fn results_stream() {
+        let words = TrieHashMap::from_words(&["ab", "ba"]);
+        let emitted = (Mutex::new(0), Condvar::new());
+        let input = SlowInput {
+            boards: &["ab\n\n", "# second\nba\n\n", "zz\n"],
+            next: 0,
+            emitted: &emitted,
+        };
+        let mut ids = Vec::new();
+        solve_batch(
+            BufReader::new(input),
+            "slow",
+            2,
+            |board| Ok(find_all(&words, board)?.len()),
+            |result| {
+                ids.push((result.id, result.outcome.unwrap()));
+                *emitted.0.lock().unwrap() += 1;
+                emitted.1.notify_all();
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            ids,
+            vec![
+                ("1".to_string(), 2),
+                ("second".to_string(), 2),
+                ("3".to_string(), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn many_boards_in_order() {
+        let words = TrieHashMap::from_words(&["ab", "ba"]);
+        let input = "ab\n\nzz\n\n".repeat(200);
+        let mut ids = Vec::new();
+        solve_batch(
+            input.as_bytes(),
+            "batch",
+            4,
+            |board| Ok(find_all(&words, board)?.len()),
+            |result| ids.push((result.id, result.outcome.unwrap())),
+        )
+        .unwrap();
+        assert_eq!(ids.len(), 400);
+        for (i, (id, found)) in ids.into_iter().enumerate() {
+            assert_eq!(
+                (id, found),
+                ((i + 1).to_string(), if i % 2 == 0 { 2 } else { 0 })
+            );
+        }
+    }
+}
@@ -0,0 +1,509 @@
+use std::fmt;
+
+use crate::normalize::Normalizer;
+
+// A single cell on the board. Real dice have faces like "Qu", "Th" and "In",
+// so a tile can hold more than one letter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Tile {
+    Letters(String),
+    // Stands for any single letter, written as '?'
+    Wildcard,
+    // A dead cell that no word can pass through
+    Blank,
+}
+
+impl Tile {
+    pub fn letters(&self) -> Option<&str> {
+        match self {
+            Tile::Letters(s) => Some(s),
+            Tile::Wildcard | Tile::Blank => None,
+        }
+    }
+}
+
+impl From<char> for Tile {
+    fn from(c: char) -> Tile {
+        if c.is_alphabetic() {
+            Tile::Letters(c.to_string())
+        } else if c == '?' {
+            Tile::Wildcard
+        } else {
+            Tile::Blank
+        }
+    }
+}
+
+// Row, Col format
+pub type Pos = (usize, usize);
+
+// Neither side of a board may be longer than this
+pub const MAX_SIDE: usize = 1024;
+
+// Why some text isn't a board. Lines and columns count from 1, columns in chars.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BoardError {
+    Empty,
+    // A row with a different number of tiles than the first row
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    // From Board::new, columns count tiles, since there is no text
+    Unsupported {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+    // A letter tile with no letters in it. Columns count tiles, since there is no text.
+    EmptyTile {
+        line: usize,
+        column: usize,
+    },
+    // The first line or column past MAX_SIDE
+    TooLarge {
+        line: usize,
+        column: usize,
+    },
+    // A hex row indented when it should line up with the first row, or the other way round
+    Misaligned {
+        line: usize,
+    },
+    // A layer of a 3D board with different dimensions than the first layer, as (rows, cols).
+    // line is the layer's first line.
+    MismatchedLayer {
+        line: usize,
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+}
+
+impl BoardError {
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            BoardError::Ragged { line, .. }
+            | BoardError::Unsupported { line, .. }
+            | BoardError::EmptyTile { line, .. }
+            | BoardError::TooLarge { line, .. }
+            | BoardError::Misaligned { line }
+            | BoardError::MismatchedLayer { line, .. } => Some(*line),
+            BoardError::Empty => None,
+        }
+    }
+
+    // The same error, reported against the given line
+    pub fn on_line(self, line: usize) -> BoardError {
+        match self {
+            BoardError::Ragged {
+                expected, found, ..
+            } => BoardError::Ragged {
+                line,
+                expected,
+                found,
+            },
+            BoardError::Unsupported { column, found, .. } => BoardError::Unsupported {
+                line,
+                column,
+                found,
+            },
+            BoardError::EmptyTile { column, .. } => BoardError::EmptyTile { line, column },
+            BoardError::TooLarge { column, .. } => BoardError::TooLarge { line, column },
+            BoardError::Misaligned { .. } => BoardError::Misaligned { line },
+            BoardError::MismatchedLayer {
+                expected, found, ..
+            } => BoardError::MismatchedLayer {
+                line,
+                expected,
+                found,
+            },
+            BoardError::Empty => BoardError::Empty,
+        }
+    }
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoardError::Empty => write!(f, "board is empty"),
+            BoardError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: row has {} tiles but the first row has {}",
+                line, found, expected
+            ),
+            BoardError::Unsupported {
+                line,
+                column,
+                found,
+            } => write!(
+                f,
+                "line {}, column {}: unsupported character {:?}",
+                line, column, found
+            ),
+            BoardError::EmptyTile { line, column } => {
+                write!(f, "line {}, tile {}: tile has no letters", line, column)
+            }
+            BoardError::TooLarge { line, column } => write!(
+                f,
+                "line {}, column {}: board is larger than {}x{}",
+                line, column, MAX_SIDE, MAX_SIDE
+            ),
+            BoardError::Misaligned { line } => write!(
+                f,
+                "line {}: row indentation doesn't alternate with the rows above",
+                line
+            ),
+            BoardError::MismatchedLayer {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: layer is {}x{} but the first layer is {}x{}",
+                line, found.0, found.1, expected.0, expected.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+// A rectangular board of at least one tile and at most MAX_SIDE on a side. The solver only
+// takes boards built through here, so it never has to check for missing cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Board {
+    rows: Vec<Vec<Tile>>,
+}
+
+impl Board {
+    pub fn new(rows: Vec<Vec<Tile>>) -> Result<Board, BoardError> {
+        let width = rows.first().map_or(0, Vec::len);
+        if width == 0 {
+            return Err(BoardError::Empty);
+        }
+        if rows.len() > MAX_SIDE {
+            return Err(BoardError::TooLarge {
+                line: MAX_SIDE + 1,
+                column: 1,
+            });
+        }
+        if width > MAX_SIDE {
+            return Err(BoardError::TooLarge {
+                line: 1,
+                column: MAX_SIDE + 1,
+            });
+        }
+        for (i, row) in rows.iter().enumerate() {
+            if row.len() != width {
+                return Err(BoardError::Ragged {
+                    line: i + 1,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+        }
+        check_tiles(&rows)?;
+        Ok(Board { rows })
+    }
+
+    // A board with one row per line, LF or CRLF. Trailing empty lines are ignored and letters
+    // are lower cased.
+    pub fn parse(text: &str) -> Result<Board, BoardError> {
+        Board::parse_with(text, &Normalizer::new())
+    }
+
+    pub fn parse_with(text: &str, normalizer: &Normalizer) -> Result<Board, BoardError> {
+        let mut lines: Vec<&str> = text.lines().collect();
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+        let mut rows = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            if i == MAX_SIDE {
+                return Err(BoardError::TooLarge {
+                    line: i + 1,
+                    column: 1,
+                });
+            }
+            let row = parse_row(line).map_err(|e| e.on_line(i + 1))?;
+            rows.push(row.into_iter().map(|t| normalizer.tile(t)).collect());
+        }
+        Board::new(rows).map_err(|e| match e {
+            // Report too-wide rows at the char where they overflow
+            BoardError::TooLarge { line, .. } => BoardError::TooLarge {
+                line,
+                column: char_column(lines[line - 1], MAX_SIDE, false),
+            },
+            e => e,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn rows(&self) -> &[Vec<Tile>] {
+        &self.rows
+    }
+}
+
+// One row per line in the notation parse reads, with '-' for blank cells
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in &self.rows {
+            for tile in row {
+                match tile {
+                    Tile::Letters(s) if s.chars().count() == 1 => write!(f, "{}", s)?,
+                    Tile::Letters(s) => write!(f, "[{}]", s)?,
+                    Tile::Wildcard => write!(f, "?")?,
+                    Tile::Blank => write!(f, "-")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// Check that every letter tile holds what a parsed row could: at least one letter and
+// nothing but letters. Columns in errors count tiles.
+pub fn check_tiles(rows: &[Vec<Tile>]) -> Result<(), BoardError> {
+    for (i, row) in rows.iter().enumerate() {
+        for (col, tile) in row.iter().enumerate() {
+            let letters = match tile {
+                Tile::Letters(letters) => letters,
+                Tile::Wildcard | Tile::Blank => continue,
+            };
+            // An empty tile would advance the trie by nothing, letting paths pass through it
+            if letters.is_empty() {
+                return Err(BoardError::EmptyTile {
+                    line: i + 1,
+                    column: col + 1,
+                });
+            }
+            if let Some(found) = letters.chars().find(|c| !c.is_alphabetic()) {
+                return Err(BoardError::Unsupported {
+                    line: i + 1,
+                    column: col + 1,
+                    found,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+// The char column (from 1) where the tile after `tiles` tiles starts. If spaced, whitespace
+// only separates tiles, as in parse_spaced_row.
+pub fn char_column(line: &str, tiles: usize, spaced: bool) -> usize {
+    let mut chars = line
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| !(spaced && c.is_whitespace()));
+    for _ in 0..tiles {
+        if let Some((_, '[')) = chars.next() {
+            chars.by_ref().find(|(_, c)| *c == ']');
+        }
+    }
+    chars
+        .next()
+        .map_or(line.chars().count() + 1, |(i, _)| i + 1)
+}
+
+// One row of tiles, one per char. Multi-letter tiles are written in brackets, e.g. "a[qu]b",
+// '?' is a wildcard and ' ', '-', '.', '_' and '#' are blank cells.
+// Errors are reported on line 1; see BoardError::on_line.
+pub fn parse_row(line: &str) -> Result<Vec<Tile>, BoardError> {
+    parse_tiles(line, false)
+}
+
+// Like parse_row, but whitespace only separates tiles
+pub fn parse_spaced_row(line: &str) -> Result<Vec<Tile>, BoardError> {
+    parse_tiles(line, true)
+}
+
+fn parse_tiles(line: &str, skip_whitespace: bool) -> Result<Vec<Tile>, BoardError> {
+    let unsupported = |column, found| BoardError::Unsupported {
+        line: 1,
+        column: column + 1,
+        found,
+    };
+    let mut tiles = Vec::new();
+    let mut chars = line.chars().enumerate();
+    while let Some((column, c)) = chars.next() {
+        match c {
+            '[' => {
+                let mut letters = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, ']')) if !letters.is_empty() => break,
+                        Some((_, c)) if c.is_alphabetic() => letters.push(c),
+                        Some((i, c)) => return Err(unsupported(i, c)),
+                        None => return Err(unsupported(column, '[')),
+                    }
+                }
+                tiles.push(Tile::Letters(letters));
+            }
+            c if skip_whitespace && c.is_whitespace() => {}
+            c if c.is_alphabetic() || matches!(c, '?' | ' ' | '-' | '.' | '_' | '#') => {
+                tiles.push(Tile::from(c))
+            }
+            c => return Err(unsupported(column, c)),
+        }
+    }
+    Ok(tiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters(s: &str) -> Tile {
+        Tile::Letters(s.to_string())
+    }
+
+    #[test]
+    fn parse_tiles() {
+        assert_eq!(
+            parse_row("a[qu]- b"),
+            Ok(vec![
+                letters("a"),
+                letters("qu"),
+                Tile::Blank,
+                Tile::Blank,
+                letters("b")
+            ])
+        );
+        assert_eq!(
+            parse_spaced_row(" a [qu] - "),
+            Ok(vec![letters("a"), letters("qu"), Tile::Blank])
+        );
+        assert_eq!(
+            Board::parse("[th]e\n[in]?\n\n").unwrap().rows(),
+            &[
+                vec![letters("th"), letters("e")],
+                vec![letters("in"), Tile::Wildcard],
+            ]
+        );
+    }
+
+    #[test]
+    fn normalized() {
+        let board = Board::parse("Ab\r\n[QU]É\r\n").unwrap();
+        assert_eq!(
+            board.rows(),
+            &[
+                vec![letters("a"), letters("b")],
+                vec![letters("qu"), letters("é")],
+            ]
+        );
+        let folded = Board::parse_with("Ab\n[QU]É", &Normalizer::new().fold_accents()).unwrap();
+        assert_eq!(folded.rows()[1], vec![letters("qu"), letters("e")]);
+        let kept = Board::parse_with("Ab\n[QU]É", &Normalizer::new().keep_case()).unwrap();
+        assert_eq!(kept.rows()[1], vec![letters("QU"), letters("É")]);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let board = Board::parse("a[qu] \n?#é").unwrap();
+        assert_eq!(board.to_string(), "a[qu]-\n?-é\n");
+        assert_eq!(Board::parse(&board.to_string()), Ok(board));
+    }
+
+    #[test]
+    fn bad_boards() {
+        assert_eq!(Board::parse(""), Err(BoardError::Empty));
+        assert_eq!(Board::parse("\n\n"), Err(BoardError::Empty));
+        assert_eq!(Board::new(vec![]), Err(BoardError::Empty));
+        assert_eq!(
+            Board::parse("abc\nd[qu]f\ngh"),
+            Err(BoardError::Ragged {
+                line: 3,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            Board::parse("abc\n\ndef"),
+            Err(BoardError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 0
+            })
+        );
+        assert_eq!(
+            Board::parse("abc\nd3f"),
+            Err(BoardError::Unsupported {
+                line: 2,
+                column: 2,
+                found: '3'
+            })
+        );
+        assert_eq!(
+            Board::parse("a[q1]"),
+            Err(BoardError::Unsupported {
+                line: 1,
+                column: 4,
+                found: '1'
+            })
+        );
+        assert_eq!(
+            Board::parse("ab[qu"),
+            Err(BoardError::Unsupported {
+                line: 1,
+                column: 3,
+                found: '['
+            })
+        );
+        assert!(Board::parse("a[]").is_err());
+        assert_eq!(
+            Board::new(vec![
+                vec![letters("a"), letters("b")],
+                vec![letters("c"), letters("")],
+            ]),
+            Err(BoardError::EmptyTile { line: 2, column: 2 })
+        );
+        assert_eq!(
+            Board::new(vec![vec![letters("a"), letters("q u")]]),
+            Err(BoardError::Unsupported {
+                line: 1,
+                column: 2,
+                found: ' '
+            })
+        );
+        assert_eq!(
+            Board::new(vec![vec![letters("4")]]),
+            Err(BoardError::Unsupported {
+                line: 1,
+                column: 1,
+                found: '4'
+            })
+        );
+        assert_eq!(
+            Board::parse(&"a\n".repeat(MAX_SIDE + 1)),
+            Err(BoardError::TooLarge {
+                line: MAX_SIDE + 1,
+                column: 1
+            })
+        );
+        assert_eq!(
+            Board::parse(&format!("[qu]{}", "a".repeat(MAX_SIDE))),
+            Err(BoardError::TooLarge {
+                line: 1,
+                column: MAX_SIDE + 4
+            })
+        );
+        assert_eq!(
+            Board::parse("ab\ncd\ne").unwrap_err().to_string(),
+            "line 3: row has 1 tiles but the first row has 2"
+        );
+    }
+}
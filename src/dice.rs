@@ -0,0 +1,341 @@
+use std::fs;
+
+use crate::board::{parse_row, Board, Tile, MAX_SIDE};
+use crate::error::Error;
+
+// Small seedable RNG (SplitMix64), so generated boards can be reproduced from a seed
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in 0..n
+    pub fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    // Uniform in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// A set of dice for a square board, one die per cell
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiceSet {
+    dice: Vec<Vec<Tile>>,
+    size: usize,
+}
+
+// One die per entry, using the board's tile notation: multi-letter faces in brackets
+// and '#' for a blank face
+const CLASSIC: [&str; 16] = [
+    "aaeegn",
+    "abbjoo",
+    "achops",
+    "affkps",
+    "aoottw",
+    "cimotu",
+    "deilrx",
+    "delrvy",
+    "distty",
+    "eeghnw",
+    "eeinsu",
+    "ehrtvw",
+    "eiosst",
+    "elrtty",
+    "himnu[qu]",
+    "hlnnrz",
+];
+
+const CLASSIC_OLD: [&str; 16] = [
+    "aaciot",
+    "abilty",
+    "abjmo[qu]",
+    "acdemp",
+    "acelrs",
+    "adenvz",
+    "ahmors",
+    "biforx",
+    "denosw",
+    "dknotu",
+    "eefhiy",
+    "egkluy",
+    "egintv",
+    "ehinps",
+    "elpstu",
+    "gilruw",
+];
+
+const BIG_BOGGLE: [&str; 25] = [
+    "aaafrs",
+    "aaeeee",
+    "aafirs",
+    "adennn",
+    "aeeeem",
+    "aeegmu",
+    "aegmnn",
+    "afirsy",
+    "bjk[qu]xz",
+    "ccenst",
+    "ceiilt",
+    "ceilpt",
+    "ceipst",
+    "ddhnot",
+    "dhhlor",
+    "dhlnor",
+    "dhlnor",
+    "eiiitt",
+    "emottt",
+    "ensssu",
+    "fiprsy",
+    "gorrvw",
+    "iprrry",
+    "nootuw",
+    "ooottu",
+];
+
+const SUPER_BIG_BOGGLE: [&str; 36] = [
+    "aaafrs",
+    "aaeeee",
+    "aaeeoo",
+    "aafirs",
+    "abdeio",
+    "adennn",
+    "aeeeem",
+    "aeegmu",
+    "aegmnn",
+    "aeilmn",
+    "aeinou",
+    "afirsy",
+    "[an][er][he][in][qu][th]",
+    "bbjkxz",
+    "ccenst",
+    "cddlnn",
+    "ceiitt",
+    "ceipst",
+    "cfgnuy",
+    "ddhnot",
+    "dhhlor",
+    "dhhnow",
+    "dhlnor",
+    "ehilrs",
+    "eiilst",
+    "eilpst",
+    "eio###",
+    "emttto",
+    "ensssu",
+    "gorrvw",
+    "hirstv",
+    "hoprst",
+    "iprsyy",
+    "jk[qu]wxz",
+    "nootuw",
+    "ooottu",
+];
+
+impl DiceSet {
+    // Classic 4x4 Boggle, current dice
+    pub fn classic() -> DiceSet {
+        DiceSet::from_faces(&CLASSIC)
+    }
+
+    // Classic 4x4 Boggle, dice from before 1987
+    pub fn classic_old() -> DiceSet {
+        DiceSet::from_faces(&CLASSIC_OLD)
+    }
+
+    // Big Boggle, 5x5
+    pub fn big_boggle() -> DiceSet {
+        DiceSet::from_faces(&BIG_BOGGLE)
+    }
+
+    // Super Big Boggle, 6x6
+    pub fn super_big_boggle() -> DiceSet {
+        DiceSet::from_faces(&SUPER_BIG_BOGGLE)
+    }
+
+    fn from_faces(dice: &[&str]) -> DiceSet {
+        DiceSet::parse(&dice.join("\n")).expect("built in dice sets are square")
+    }
+
+    // One die per line, e.g. "aaeegn" or "himnu[qu]". Letters are lowercased and blank
+    // lines are skipped. The number of dice has to fill a square board, or it's
+    // Error::DiceCount; a face that isn't a tile is an Error::Board on the die's line.
+    pub fn parse(text: &str) -> Result<DiceSet, Error> {
+        let mut dice = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let faces = parse_row(&line.trim().to_lowercase()).map_err(|e| e.on_line(i + 1))?;
+            dice.push(faces);
+        }
+        let size = (dice.len() as f64).sqrt().round() as usize;
+        if dice.is_empty() || size * size != dice.len() || size > MAX_SIDE {
+            return Err(Error::DiceCount { dice: dice.len() });
+        }
+        Ok(DiceSet { dice, size })
+    }
+
+    // Like parse, but a die that doesn't parse is an Error::MalformedLine, as in dictionaries
+    pub fn from_file(filename: &str) -> Result<DiceSet, Error> {
+        let text = fs::read_to_string(filename).map_err(|source| Error::Io {
+            path: filename.to_string(),
+            source,
+        })?;
+        DiceSet::parse(&text).map_err(|e| match e {
+            Error::Board(e) => match e.line() {
+                Some(line) => Error::MalformedLine {
+                    path: filename.to_string(),
+                    line,
+                },
+                None => Error::Board(e),
+            },
+            e => e,
+        })
+    }
+
+    // Width (and height) of the board these dice fill
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn dice(&self) -> &[Vec<Tile>] {
+        &self.dice
+    }
+
+    // Shake the dice into the grid and roll each one
+    pub fn roll(&self, rng: &mut Rng) -> Board {
+        let mut order: Vec<usize> = (0..self.dice.len()).collect();
+        rng.shuffle(&mut order);
+        let rows = order
+            .chunks(self.size)
+            .map(|row| {
+                row.iter()
+                    .map(|&die| {
+                        let faces = &self.dice[die];
+                        faces[rng.below(faces.len())].clone()
+                    })
+                    .collect()
+            })
+            .collect();
+        Board::new(rows).expect("parse only accepts dice that fill a square board")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardError;
+
+    #[test]
+    fn presets() {
+        for (dice, size) in [
+            (DiceSet::classic(), 4),
+            (DiceSet::classic_old(), 4),
+            (DiceSet::big_boggle(), 5),
+            (DiceSet::super_big_boggle(), 6),
+        ] {
+            assert_eq!(dice.size(), size);
+            assert!(dice.dice().iter().all(|die| die.len() == 6));
+        }
+    }
+
+    #[test]
+    fn seeded_rolls() {
+        let dice = DiceSet::classic();
+        let board = dice.roll(&mut Rng::new(42));
+        assert_eq!(board, dice.roll(&mut Rng::new(42)));
+        assert_ne!(board, dice.roll(&mut Rng::new(43)));
+        assert_eq!((board.width(), board.height()), (4, 4));
+    }
+
+    #[test]
+    fn every_die_used_once() {
+        let dice = DiceSet::parse("aaaaaa\nbbbbbb\ncccccc\ndddddd").unwrap();
+        for seed in 0..20 {
+            let mut letters: Vec<String> = dice
+                .roll(&mut Rng::new(seed))
+                .rows()
+                .iter()
+                .flatten()
+                .map(|tile| tile.letters().unwrap().to_string())
+                .collect();
+            letters.sort();
+            assert_eq!(letters, vec!["a", "b", "c", "d"]);
+        }
+    }
+
+    #[test]
+    fn custom_dice() {
+        let dice = DiceSet::parse("AB\ncd\n\n[Qu]#\nef\n").unwrap();
+        assert_eq!(dice.size(), 2);
+        assert_eq!(
+            dice.dice()[2],
+            vec![Tile::Letters("qu".to_string()), Tile::Blank]
+        );
+        assert!(matches!(
+            DiceSet::parse("ab\ncd\nef"),
+            Err(Error::DiceCount { dice: 3 })
+        ));
+        assert!(matches!(
+            DiceSet::parse(""),
+            Err(Error::DiceCount { dice: 0 })
+        ));
+        assert!(matches!(
+            DiceSet::parse("ab\nc1"),
+            Err(Error::Board(BoardError::Unsupported {
+                line: 2,
+                column: 2,
+                found: '1'
+            }))
+        ));
+    }
+
+    #[test]
+    fn dice_files() {
+        let path = std::env::temp_dir().join(format!("boggle-dice-{}", std::process::id()));
+        let path = path.to_str().unwrap();
+        fs::write(path, "ab\ncd\n\nef\ng!\n").unwrap();
+        let bad = DiceSet::from_file(path);
+        fs::write(path, "ab\ncd\nef\ngh\n").unwrap();
+        let good = DiceSet::from_file(path);
+        fs::remove_file(path).unwrap();
+        assert!(matches!(bad, Err(Error::MalformedLine { line: 5, .. })));
+        assert_eq!(good.unwrap().size(), 2);
+        assert!(matches!(
+            DiceSet::from_file("./no-such-dice.txt"),
+            Err(Error::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn rng_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!(rng.below(6) < 6);
+            let f = rng.next_f64();
+            assert!((0.0..1.0).contains(&f));
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use std::{fmt, io};
+
+use crate::board::BoardError;
+use crate::solver::Unplayable;
+
+// Everything that can go wrong loading a dictionary or board, or solving one
+#[derive(Debug)]
+pub enum Error {
+    // Opening or reading a file failed
+    Io { path: String, source: io::Error },
+    // A dictionary line that isn't a single word, e.g. bad UTF-8 or two words on one line,
+    // or a line of a dice file that isn't a die. Lines count from 1.
+    MalformedLine { path: String, line: usize },
+    // A character the dictionary has no room for
+    UnsupportedChar { word: String, found: char },
+    // A dice set with a number of dice that can't fill a square board
+    DiceCount { dice: usize },
+    Board(BoardError),
+    Unplayable(Unplayable),
+    // The grid has more cells than the solver will index
+    TooManyCells { cells: usize, limit: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "{}: {}", path, source),
+            Error::MalformedLine { path, line } => {
+                write!(f, "{}: line {} is malformed", path, line)
+            }
+            Error::UnsupportedChar { word, found } => {
+                write!(f, "unsupported character {:?} in {:?}", found, word)
+            }
+            Error::DiceCount { dice } => {
+                write!(f, "{} dice can't fill a square board", dice)
+            }
+            Error::Board(e) => write!(f, "bad board: {}", e),
+            Error::Unplayable(Unplayable::NotAWord) => write!(f, "not a word"),
+            Error::Unplayable(Unplayable::NotOnBoard) => write!(f, "not on the board"),
+            Error::Unplayable(Unplayable::TooShort) => write!(f, "too short"),
+            Error::Unplayable(Unplayable::AlreadyPlayed) => write!(f, "already played"),
+            Error::Unplayable(Unplayable::TimeUp) => write!(f, "time is up"),
+            Error::TooManyCells { cells, limit } => {
+                write!(
+                    f,
+                    "board has {} cells, the solver takes at most {}",
+                    cells, limit
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Board(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<BoardError> for Error {
+    fn from(e: BoardError) -> Error {
+        Error::Board(e)
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::board::Board;
+use crate::error::Error;
+use crate::normalize::Normalizer;
+use crate::scoring::{Boggle, ScoringRule};
+use crate::solver::{find_all_best_paths, find_paths, Unplayable};
+use crate::word_dict::Trie;
+
+// Where a game gets the time from. Only the difference between two readings matters, so a
+// test can hand in a clock it moves by hand.
+pub trait Clock {
+    fn now(&self) -> Duration;
+}
+
+// Real time, counted from when the clock was made
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> SystemClock {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> SystemClock {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+}
+
+// What counts as a playable word and what it's worth
+pub struct Rules<'r> {
+    // In letters
+    pub min_len: usize,
+    pub scoring: &'r dyn ScoringRule,
+}
+
+impl Rules<'static> {
+    // Classic Boggle: at least 3 letters, scored with scoring::Boggle
+    pub fn boggle() -> Rules<'static> {
+        Rules {
+            min_len: 3,
+            scoring: &Boggle,
+        }
+    }
+}
+
+// How a finished game went. Words are paired with their points; found is in the order the
+// words were played and missed is alphabetical.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Summary {
+    pub score: u32,
+    pub found: Vec<(String, u32)>,
+    pub missed: Vec<(String, u32)>,
+}
+
+// One player's timed game on one board. The countdown starts when the session is made.
+pub struct GameSession<'a, T, C = SystemClock> {
+    words: &'a T,
+    board: Board,
+    rules: Rules<'a>,
+    clock: C,
+    ends_at: Duration,
+    normalizer: Normalizer,
+    found: Vec<(String, u32)>,
+    played: HashSet<String>,
+}
+
+impl<'a, T: Trie, C: Clock> GameSession<'a, T, C> {
+    pub fn new(
+        words: &'a T,
+        board: Board,
+        rules: Rules<'a>,
+        time_limit: Duration,
+        clock: C,
+    ) -> GameSession<'a, T, C> {
+        let ends_at = clock.now() + time_limit;
+        GameSession {
+            words,
+            board,
+            rules,
+            clock,
+            ends_at,
+            normalizer: Normalizer::new(),
+            found: Vec::new(),
+            played: HashSet::new(),
+        }
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    // Time left on the countdown, zero once it has run out
+    pub fn remaining(&self) -> Duration {
+        self.ends_at.saturating_sub(self.clock.now())
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    // Play a word, normalized the same way as the dictionary, and get its points. A word
+    // on more than one path scores its best path. Rejected words fail with
+    // Error::Unplayable and don't count against the player.
+    pub fn submit(&mut self, word: &str) -> Result<u32, Error> {
+        if self.is_over() {
+            return Err(Error::Unplayable(Unplayable::TimeUp));
+        }
+        let word = self.normalizer.word(self.normalizer.line(word));
+        if self.played.contains(&word) {
+            return Err(Error::Unplayable(Unplayable::AlreadyPlayed));
+        }
+        if word.chars().count() < self.rules.min_len {
+            return Err(Error::Unplayable(Unplayable::TooShort));
+        }
+        let points = find_paths(self.words, &self.board, &word)?
+            .iter()
+            .map(|path| self.rules.scoring.score(&word, path))
+            .max()
+            .unwrap_or(0);
+        self.played.insert(word.clone());
+        self.found.push((word, points));
+        Ok(points)
+    }
+
+    // Words played so far, with their points
+    pub fn found(&self) -> &[(String, u32)] {
+        &self.found
+    }
+
+    pub fn score(&self) -> u32 {
+        self.found.iter().map(|(_, points)| points).sum()
+    }
+
+    // End the game, whether or not time is up, and list the words the player missed
+    pub fn finish(self) -> Result<Summary, Error> {
+        let missed = find_all_best_paths(self.words, &self.board, self.rules.scoring)?
+            .into_iter()
+            .filter(|(word, _, _)| {
+                word.chars().count() >= self.rules.min_len && !self.played.contains(word)
+            })
+            .map(|(word, _, points)| (word, points))
+            .collect();
+        Ok(Summary {
+            score: self.score(),
+            found: self.found,
+            missed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::word_dict::hashmap::TrieHashMap;
+
+    // A clock that only moves when told to
+    struct TestClock(Cell<Duration>);
+
+    impl TestClock {
+        fn advance(&self, by: Duration) {
+            self.0.set(self.0.get() + by);
+        }
+    }
+
+    impl Clock for TestClock {
+        fn now(&self) -> Duration {
+            self.0.get()
+        }
+    }
+
+    fn rejection(result: Result<u32, Error>) -> Unplayable {
+        match result {
+            Err(Error::Unplayable(reason)) => reason,
+            other => panic!("expected a rejection, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn timed_game() {
+        let words = TrieHashMap::from_words(&["he", "hell", "hello", "lo", "ole", "yell"]);
+        let board = Board::parse("xyq\nh o\nell").unwrap();
+        let clock = TestClock(Cell::new(Duration::from_secs(100)));
+        let mut game = GameSession::new(
+            &words,
+            board.clone(),
+            Rules::boggle(),
+            Duration::from_secs(60),
+            &clock,
+        );
+        assert_eq!(game.board(), &board);
+
+        assert_eq!(game.submit("HELL\n").unwrap(), 1);
+        assert_eq!(rejection(game.submit("hell")), Unplayable::AlreadyPlayed);
+        assert_eq!(rejection(game.submit("he")), Unplayable::TooShort);
+        assert_eq!(rejection(game.submit("xyz")), Unplayable::NotAWord);
+        assert_eq!(rejection(game.submit("yell")), Unplayable::NotOnBoard);
+        assert_eq!(game.found(), &[("hell".to_string(), 1)]);
+        assert_eq!(game.score(), 1);
+
+        clock.advance(Duration::from_secs(45));
+        assert_eq!(game.remaining(), Duration::from_secs(15));
+        assert!(!game.is_over());
+        clock.advance(Duration::from_secs(20));
+        assert!(game.is_over());
+        assert_eq!(game.remaining(), Duration::ZERO);
+        assert_eq!(rejection(game.submit("hello")), Unplayable::TimeUp);
+
+        assert_eq!(
+            game.finish().unwrap(),
+            Summary {
+                score: 1,
+                found: vec![("hell".to_string(), 1)],
+                missed: vec![("hello".to_string(), 2), ("ole".to_string(), 1)],
+            }
+        );
+    }
+
+    #[test]
+    fn custom_rules() {
+        use crate::scoring::WordHunt;
+
+        let words = TrieHashMap::from_words(&["he", "hell", "lo"]);
+        let board = Board::parse("xyq\nh o\nell").unwrap();
+        let rules = Rules {
+            min_len: 2,
+            scoring: &WordHunt,
+        };
+        let clock = TestClock(Cell::new(Duration::ZERO));
+        let mut game = GameSession::new(&words, board, rules, Duration::from_secs(1), &clock);
+        assert_eq!(game.submit("lo").unwrap(), 0);
+        assert_eq!(game.submit("hell").unwrap(), 400);
+        let summary = game.finish().unwrap();
+        assert_eq!(summary.score, 400);
+        assert_eq!(summary.missed, vec![("he".to_string(), 0)]);
+    }
+}
@@ -0,0 +1,200 @@
+use super::Grid;
+use crate::board::{Board, BoardError, Tile, MAX_SIDE};
+use crate::normalize::Normalizer;
+
+// Layer, Row, Col format
+pub type CubePos = (usize, usize, usize);
+
+// A 3D board made of equally sized square-grid layers stacked on top of each other.
+// Every cell touches the up to 26 cells around it, including across layers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CubeBoard {
+    layers: Vec<Board>,
+}
+
+impl CubeBoard {
+    // A mismatched layer is reported with its layer number, counting from 1, as its line
+    pub fn new(layers: Vec<Board>) -> Result<CubeBoard, BoardError> {
+        let first = layers.first().ok_or(BoardError::Empty)?;
+        if layers.len() > MAX_SIDE {
+            return Err(BoardError::TooLarge { line: 1, column: 1 });
+        }
+        for (i, layer) in layers.iter().enumerate() {
+            same_size(first, layer, i + 1)?;
+        }
+        Ok(CubeBoard { layers })
+    }
+
+    // Layers are written like 2D boards, top layer first, separated by blank lines:
+    //
+    //     ab
+    //     cd
+    //
+    //     ef
+    //     gh
+    //
+    // A row of all blank tiles has to use '-' rather than spaces, or it reads as a separator.
+    // Errors are reported against lines of the whole text.
+    pub fn parse(text: &str) -> Result<CubeBoard, BoardError> {
+        CubeBoard::parse_with(text, &Normalizer::new())
+    }
+
+    pub fn parse_with(text: &str, normalizer: &Normalizer) -> Result<CubeBoard, BoardError> {
+        let mut layers = Vec::new();
+        let mut block = String::new();
+        let mut block_line = 0;
+        let lines = text.lines().chain(std::iter::once(""));
+        for (i, line) in lines.enumerate() {
+            if !line.trim().is_empty() {
+                if block.is_empty() {
+                    block_line = i + 1;
+                }
+                block.push_str(line);
+                block.push('\n');
+                continue;
+            }
+            if block.is_empty() {
+                continue;
+            }
+            let shift = |e: BoardError| match e.line() {
+                Some(line) => e.on_line(line + block_line - 1),
+                None => e,
+            };
+            let layer = Board::parse_with(&block, normalizer).map_err(shift)?;
+            if layers.len() == MAX_SIDE {
+                return Err(BoardError::TooLarge {
+                    line: block_line,
+                    column: 1,
+                });
+            }
+            if let Some(first) = layers.first() {
+                same_size(first, &layer, block_line)?;
+            }
+            layers.push(layer);
+            block.clear();
+        }
+        CubeBoard::new(layers)
+    }
+
+    pub fn layers(&self) -> &[Board] {
+        &self.layers
+    }
+}
+
+// MismatchedLayer at line unless layer has the same dimensions as first
+fn same_size(first: &Board, layer: &Board, line: usize) -> Result<(), BoardError> {
+    let expected = (first.height(), first.width());
+    let found = (layer.height(), layer.width());
+    if found == expected {
+        Ok(())
+    } else {
+        Err(BoardError::MismatchedLayer {
+            line,
+            expected,
+            found,
+        })
+    }
+}
+
+impl Grid for CubeBoard {
+    type Pos = CubePos;
+
+    fn positions(&self) -> Vec<CubePos> {
+        let (height, width) = (self.layers[0].height(), self.layers[0].width());
+        (0..self.layers.len())
+            .flat_map(|layer| {
+                (0..height).flat_map(move |row| (0..width).map(move |col| (layer, row, col)))
+            })
+            .collect()
+    }
+
+    fn tile(&self, pos: CubePos) -> &Tile {
+        &self.layers[pos.0].rows()[pos.1][pos.2]
+    }
+
+    fn neighbors(&self, pos: CubePos) -> Vec<CubePos> {
+        let depth = self.layers.len();
+        let (height, width) = (self.layers[0].height(), self.layers[0].width());
+        let steps = |at: usize, size: usize| at.saturating_sub(1)..(at + 2).min(size);
+        let mut neighbors = Vec::with_capacity(26);
+        for layer in steps(pos.0, depth) {
+            for row in steps(pos.1, height) {
+                for col in steps(pos.2, width) {
+                    if (layer, row, col) != pos {
+                        neighbors.push((layer, row, col));
+                    }
+                }
+            }
+        }
+        neighbors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{find_all, find_paths};
+    use crate::word_dict::hashmap::TrieHashMap;
+    use crate::word_dict::vec::TrieVec;
+
+    #[test]
+    fn parse_layers() {
+        let cube = CubeBoard::parse("ab\ncd\n\n\nEF\ngh\n").unwrap();
+        assert_eq!(cube.layers().len(), 2);
+        assert_eq!(cube.tile((1, 0, 0)), &Tile::Letters("e".to_string()));
+        assert_eq!(cube.positions().len(), 8);
+        assert_eq!(CubeBoard::parse("\n \n"), Err(BoardError::Empty));
+        assert_eq!(
+            CubeBoard::parse("ab\ncd\n\nef\ngh\n\nij\n"),
+            Err(BoardError::MismatchedLayer {
+                line: 7,
+                expected: (2, 2),
+                found: (1, 2)
+            })
+        );
+        assert_eq!(
+            CubeBoard::parse("ab\ncd\n\nef\ng"),
+            Err(BoardError::Ragged {
+                line: 5,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert!(matches!(
+            CubeBoard::parse("ab\n\nc!"),
+            Err(BoardError::Unsupported { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn neighbors() {
+        let cube = CubeBoard::parse(&"abcd\nefgh\nijkl\nmnop\n\n".repeat(4)).unwrap();
+        assert_eq!(cube.neighbors((0, 0, 0)).len(), 7);
+        assert_eq!(cube.neighbors((1, 1, 1)).len(), 26);
+        assert_eq!(cube.neighbors((3, 0, 2)).len(), 11);
+        assert_eq!(cube.neighbors((1, 0, 0)).len(), 11);
+        assert!(!cube.neighbors((1, 1, 1)).contains(&(1, 1, 1)));
+        assert!(cube.neighbors((1, 1, 1)).contains(&(2, 2, 2)));
+        assert!(!cube.neighbors((0, 0, 0)).contains(&(2, 0, 0)));
+    }
+
+    #[test]
+    fn solve_through_layers() {
+        // c-a-t only connects by going down through all three layers
+        let cube = CubeBoard::parse("cx\nxx\n\nxa\nxx\n\nxx\nxt").unwrap();
+        let words = TrieHashMap::from_words(&["cat"]);
+        let found = find_all(&words, &cube).unwrap();
+        assert_eq!(
+            found,
+            vec![("cat".to_string(), vec![(0, 0, 0), (1, 0, 1), (2, 1, 1)])]
+        );
+        assert_eq!(
+            find_paths(&words, &cube, "cat").unwrap(),
+            vec![vec![(0, 0, 0), (1, 0, 1), (2, 1, 1)]]
+        );
+        // Any Trie backend works
+        let mut words: TrieVec = TrieVec::blank();
+        words.add_word("cat").unwrap();
+        assert_eq!(find_all(&words, &cube).unwrap(), found);
+    }
+}
@@ -0,0 +1,273 @@
+use super::Grid;
+use crate::board::{char_column, check_tiles, parse_spaced_row, BoardError, Tile, MAX_SIDE};
+use crate::normalize::Normalizer;
+
+// Axial (q, r) format
+pub type HexPos = (isize, isize);
+
+// The six axial moves, starting east and going anticlockwise
+const DIRECTIONS: [HexPos; 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+
+// A hex board stored as offset rows. If odd_shifted, odd rows sit half a cell to the
+// right of even rows ("odd-r" layout), otherwise even rows do ("even-r").
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HexBoard {
+    rows: Vec<Vec<Tile>>,
+    odd_shifted: bool,
+}
+
+impl HexBoard {
+    // Rows may differ in length, but there has to be at least one tile and at most MAX_SIDE
+    // rows and tiles in a row. The same tiles are rejected as by Board::new.
+    pub fn new(rows: Vec<Vec<Tile>>, odd_shifted: bool) -> Result<HexBoard, BoardError> {
+        if rows.iter().all(Vec::is_empty) {
+            return Err(BoardError::Empty);
+        }
+        if rows.len() > MAX_SIDE {
+            return Err(BoardError::TooLarge {
+                line: MAX_SIDE + 1,
+                column: 1,
+            });
+        }
+        if let Some(i) = rows.iter().position(|row| row.len() > MAX_SIDE) {
+            return Err(BoardError::TooLarge {
+                line: i + 1,
+                column: MAX_SIDE + 1,
+            });
+        }
+        check_tiles(&rows)?;
+        Ok(HexBoard { rows, odd_shifted })
+    }
+
+    // Parse an offset-row layout, one row per line, where shifted rows are indented:
+    //
+    //     a b c
+    //      d e f
+    //     g h i
+    //
+    // Spaces between tiles are optional and multi-letter tiles go in brackets, e.g. "[qu]".
+    // Rows may differ in length, since hex boards are often not rectangular.
+    // An indented first row means even rows are shifted, and every other row has to follow
+    // the same alternation. Letters are lower cased.
+    pub fn parse(text: &str) -> Result<HexBoard, BoardError> {
+        HexBoard::parse_with(text, &Normalizer::new())
+    }
+
+    pub fn parse_with(text: &str, normalizer: &Normalizer) -> Result<HexBoard, BoardError> {
+        let mut rows = Vec::new();
+        let mut odd_shifted = true;
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if rows.len() == MAX_SIDE {
+                return Err(BoardError::TooLarge {
+                    line: i + 1,
+                    column: 1,
+                });
+            }
+            let indented = line.starts_with(char::is_whitespace);
+            if rows.is_empty() {
+                odd_shifted = !indented;
+            } else if indented != ((rows.len() % 2 == 1) == odd_shifted) {
+                return Err(BoardError::Misaligned { line: i + 1 });
+            }
+            let row = parse_spaced_row(line).map_err(|e| e.on_line(i + 1))?;
+            if row.len() > MAX_SIDE {
+                return Err(BoardError::TooLarge {
+                    line: i + 1,
+                    column: char_column(line, MAX_SIDE, true),
+                });
+            }
+            rows.push(row.into_iter().map(|t| normalizer.tile(t)).collect());
+        }
+        HexBoard::new(rows, odd_shifted)
+    }
+
+    pub fn to_axial(&self, row: usize, col: usize) -> HexPos {
+        let (row, col) = (row as isize, col as isize);
+        let parity = row & 1;
+        if self.odd_shifted {
+            (col - (row - parity) / 2, row)
+        } else {
+            (col - (row + parity) / 2, row)
+        }
+    }
+
+    // Row, Col of this pos in the stored rows, if it is on the board
+    pub fn to_offset(&self, pos: HexPos) -> Option<(usize, usize)> {
+        let (q, r) = pos;
+        let parity = r & 1;
+        let col = if self.odd_shifted {
+            q + (r - parity) / 2
+        } else {
+            q + (r + parity) / 2
+        };
+        let row = usize::try_from(r).ok()?;
+        let col = usize::try_from(col).ok()?;
+        if col < self.rows.get(row)?.len() {
+            Some((row, col))
+        } else {
+            None
+        }
+    }
+}
+
+impl Grid for HexBoard {
+    type Pos = HexPos;
+
+    fn positions(&self) -> Vec<HexPos> {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(row, cells)| (0..cells.len()).map(move |col| self.to_axial(row, col)))
+            .collect()
+    }
+
+    fn tile(&self, pos: HexPos) -> &Tile {
+        let (row, col) = self.to_offset(pos).expect("position is not on the board");
+        &self.rows[row][col]
+    }
+
+    fn neighbors(&self, pos: HexPos) -> Vec<HexPos> {
+        DIRECTIONS
+            .iter()
+            .map(|(dq, dr)| (pos.0 + dq, pos.1 + dr))
+            .filter(|p| self.to_offset(*p).is_some())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::parse_row;
+
+    fn letter(board: &HexBoard, pos: HexPos) -> char {
+        board.tile(pos).letters().unwrap().chars().next().unwrap()
+    }
+
+    #[test]
+    fn parse_layouts() {
+        let odd = HexBoard::parse("a b c\n d e f\ng h i\n").unwrap();
+        assert_eq!(
+            odd,
+            HexBoard::new(
+                vec![
+                    parse_row("abc").unwrap(),
+                    parse_row("def").unwrap(),
+                    parse_row("ghi").unwrap(),
+                ],
+                true
+            )
+            .unwrap()
+        );
+        let even = HexBoard::parse(" A [Qu]\r\ncd\n").unwrap();
+        assert_eq!(
+            even,
+            HexBoard::new(
+                vec![parse_row("a[qu]").unwrap(), parse_row("cd").unwrap()],
+                false
+            )
+            .unwrap()
+        );
+        assert_eq!(HexBoard::parse(" \n\n"), Err(BoardError::Empty));
+        assert_eq!(
+            HexBoard::parse("abc\n def\n ghi"),
+            Err(BoardError::Misaligned { line: 3 })
+        );
+        assert_eq!(
+            HexBoard::parse(" ab\n\n cd"),
+            Err(BoardError::Misaligned { line: 3 })
+        );
+        assert_eq!(
+            HexBoard::parse(&format!("ab\n {}", "a ".repeat(MAX_SIDE + 1))),
+            Err(BoardError::TooLarge {
+                line: 2,
+                column: 2 * MAX_SIDE + 2
+            })
+        );
+        assert_eq!(
+            HexBoard::parse("a b\n c 4"),
+            Err(BoardError::Unsupported {
+                line: 2,
+                column: 4,
+                found: '4'
+            })
+        );
+    }
+
+    #[test]
+    fn new_boards() {
+        let row = |s| parse_row(s).unwrap();
+        assert_eq!(
+            HexBoard::new(vec![vec![], vec![]], true),
+            Err(BoardError::Empty)
+        );
+        assert_eq!(
+            HexBoard::new(vec![row("ab"), vec![Tile::Letters(String::new())]], true),
+            Err(BoardError::EmptyTile { line: 2, column: 1 })
+        );
+        assert_eq!(
+            HexBoard::new(vec![vec![Tile::Letters("1".to_string())]], false),
+            Err(BoardError::Unsupported {
+                line: 1,
+                column: 1,
+                found: '1'
+            })
+        );
+        assert_eq!(
+            HexBoard::new(vec![row("a"); MAX_SIDE + 1], true),
+            Err(BoardError::TooLarge {
+                line: MAX_SIDE + 1,
+                column: 1
+            })
+        );
+    }
+
+    #[test]
+    fn offset_round_trip() {
+        for board in [
+            HexBoard::parse("abc\n def\nghi").unwrap(),
+            HexBoard::parse(" abc\ndef\n ghi").unwrap(),
+        ] {
+            for pos in board.positions() {
+                let (row, col) = board.to_offset(pos).unwrap();
+                assert_eq!(board.to_axial(row, col), pos);
+            }
+        }
+    }
+
+    #[test]
+    fn neighbors() {
+        // a b c
+        //  d e f
+        // g h i
+        let board = HexBoard::parse("abc\n def\nghi").unwrap();
+        let letters = |pos: (usize, usize)| {
+            let mut v: Vec<char> = board
+                .neighbors(board.to_axial(pos.0, pos.1))
+                .into_iter()
+                .map(|p| letter(&board, p))
+                .collect();
+            v.sort();
+            v
+        };
+        assert_eq!(letters((1, 1)), vec!['b', 'c', 'd', 'f', 'h', 'i']);
+        assert_eq!(letters((0, 0)), vec!['b', 'd']);
+        assert_eq!(letters((0, 2)), vec!['b', 'e', 'f']);
+        assert_eq!(letters((1, 2)), vec!['c', 'e', 'i']);
+        assert_eq!(letters((2, 0)), vec!['d', 'h']);
+
+        //  a b
+        // c d
+        let even = HexBoard::parse(" ab\ncd").unwrap();
+        let mut v: Vec<char> = even
+            .neighbors(even.to_axial(0, 0))
+            .into_iter()
+            .map(|p| letter(&even, p))
+            .collect();
+        v.sort();
+        assert_eq!(v, vec!['b', 'c', 'd']);
+    }
+}
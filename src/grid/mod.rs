@@ -0,0 +1,18 @@
+pub mod cube;
+pub mod hex;
+pub mod square;
+
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::board::Tile;
+
+// A board the solver can walk: a set of cells, the tile on each, and which cells touch
+pub trait Grid {
+    type Pos: Copy + Eq + Hash + Debug;
+
+    // Every cell, in the order the solver should start searching from
+    fn positions(&self) -> Vec<Self::Pos>;
+    fn tile(&self, pos: Self::Pos) -> &Tile;
+    fn neighbors(&self, pos: Self::Pos) -> Vec<Self::Pos>;
+}
@@ -0,0 +1,56 @@
+use super::Grid;
+use crate::adjacency::Adjacency;
+use crate::board::{Board, Pos, Tile};
+
+// A plain board is played with standard Boggle adjacency
+impl Grid for Board {
+    type Pos = Pos;
+
+    fn positions(&self) -> Vec<Pos> {
+        square_positions(self)
+    }
+
+    fn tile(&self, pos: Pos) -> &Tile {
+        &self.rows()[pos.0][pos.1]
+    }
+
+    fn neighbors(&self, pos: Pos) -> Vec<Pos> {
+        Adjacency::standard().neighbors(self.width(), self.height(), pos)
+    }
+}
+
+// A board played with some other adjacency, e.g. orthogonal-only or wrap-around
+pub struct SquareGrid<'a> {
+    board: &'a Board,
+    adjacency: &'a Adjacency,
+}
+
+impl<'a> SquareGrid<'a> {
+    pub fn new(board: &'a Board, adjacency: &'a Adjacency) -> SquareGrid<'a> {
+        SquareGrid { board, adjacency }
+    }
+}
+
+impl Grid for SquareGrid<'_> {
+    type Pos = Pos;
+
+    fn positions(&self) -> Vec<Pos> {
+        square_positions(self.board)
+    }
+
+    fn tile(&self, pos: Pos) -> &Tile {
+        &self.board.rows()[pos.0][pos.1]
+    }
+
+    fn neighbors(&self, pos: Pos) -> Vec<Pos> {
+        self.adjacency
+            .neighbors(self.board.width(), self.board.height(), pos)
+    }
+}
+
+fn square_positions(board: &Board) -> Vec<Pos> {
+    let width = board.width();
+    (0..board.height())
+        .flat_map(|row| (0..width).map(move |col| (row, col)))
+        .collect()
+}
@@ -0,0 +1,19 @@
+#![feature(test)]
+pub mod adjacency;
+pub mod analysis;
+pub mod batch;
+pub mod board;
+pub mod dice;
+pub mod error;
+pub mod game;
+pub mod grid;
+pub mod normalize;
+pub mod optimizer;
+pub mod query;
+pub mod ranking;
+pub mod scoring;
+pub mod solver;
+pub mod word_dict;
+pub mod wordsearch;
+
+extern crate test;
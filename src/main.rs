@@ -1,32 +1,54 @@
-#![feature(test)]
-mod solver;
-mod word_dict;
-
-use std::fs::File;
 use std::io::{self, BufRead};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs, process, thread};
 
 use typed_arena::Arena;
-use word_dict::linkedlist_typedarena::TrieLinkedListArena;
-
-use crate::solver::find_best;
-use crate::word_dict::Trie;
-use crate::word_dict::hashmap::TrieHashMap;
-use crate::word_dict::linkedlist::TrieLinkedList;
-extern crate test;
 
-type Board = Vec<Vec<char>>;
-// Row, Col format
-type Pos = (usize, usize);
+use boggle::analysis::analyze;
+use boggle::batch::solve_batch;
+use boggle::board::Board;
+use boggle::dice::{DiceSet, Rng};
+use boggle::error::Error;
+use boggle::normalize::Normalizer;
+use boggle::scoring::Boggle;
+use boggle::solver::find_best;
+use boggle::word_dict::hashmap::TrieHashMap;
+use boggle::word_dict::linkedlist_typedarena::TrieLinkedListArena;
+use boggle::word_dict::Skipped;
 
 fn main() {
-    let arena = Arena::with_capacity(1027814);
-    let words = TrieLinkedListArena::from_file("./words_alpha.txt", &arena).unwrap();
+    if let Err(e) = run() {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
 
-    let mut board: Board = Vec::new();
-    let lines = io::BufReader::new(File::open("./board2.txt").unwrap()).lines();
-    for row in lines.flatten() {
-        board.push(row.chars().collect::<Vec<char>>());
+fn run() -> Result<(), Error> {
+    let args: Vec<String> = env::args().collect();
+    if args.get(1).is_some_and(|a| a == "--batch") {
+        return run_batch(args.get(2).map_or("-", String::as_str));
     }
+    if args.get(1).is_some_and(|a| a == "--roll") {
+        let dice = match args.get(2) {
+            Some(dice) => dice,
+            None => {
+                eprintln!("usage: boggle --roll DICE_FILE [SEED]");
+                process::exit(2);
+            }
+        };
+        return run_roll(dice, args.get(3).map(String::as_str));
+    }
+
+    let arena = Arena::with_capacity(1027814);
+    let (words, skipped) =
+        TrieLinkedListArena::load("./words_alpha.txt", &arena, &Normalizer::new())?;
+    report_skipped(&skipped);
+
+    let text = fs::read_to_string("./board2.txt").map_err(|source| Error::Io {
+        path: "./board2.txt".to_string(),
+        source,
+    })?;
+    let board = Board::parse(&text)?;
 
     // println!("{}", size_of::<[Box<word_dict::DictEntry>;26]>());
 
@@ -37,7 +59,67 @@ fn main() {
     //     println!("{:?}", d);
     // }
 
-    println!("{:?}", find_best(&words, &board));
+    println!("{:?}", find_best(&words, &board)?);
 
     // println!("{:?}", words.valid_word_start("oranguta".to_string()));
+    Ok(())
+}
+
+fn report_skipped(skipped: &[Skipped]) {
+    for skip in skipped {
+        eprintln!("skipped {:?}: {}", skip.text, skip.reason);
+    }
+}
+
+// boggle --roll DICE_FILE [SEED]: roll a board from a dice file, one die per line, and print
+// it with its longest word. Without a seed the clock picks one.
+fn run_roll(path: &str, seed: Option<&str>) -> Result<(), Error> {
+    let dice = DiceSet::from_file(path)?;
+    let seed = match seed {
+        Some(seed) => seed.parse().unwrap_or_else(|_| {
+            eprintln!("error: seed {:?} is not a number", seed);
+            process::exit(2);
+        }),
+        None => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64),
+    };
+    let board = dice.roll(&mut Rng::new(seed));
+    let arena = Arena::with_capacity(1027814);
+    let (words, skipped) =
+        TrieLinkedListArena::load("./words_alpha.txt", &arena, &Normalizer::new())?;
+    report_skipped(&skipped);
+    println!("seed {}", seed);
+    print!("{}", board);
+    println!("{:?}", find_best(&words, &board)?);
+    Ok(())
+}
+
+// boggle --batch [FILE]: solve every board in FILE, or stdin if it's missing or "-", printing
+// one line per board as it's solved
+fn run_batch(path: &str) -> Result<(), Error> {
+    // The arena trie isn't Sync, so batches share a hashmap trie between threads
+    let (words, skipped) = TrieHashMap::load("./words_alpha.txt", &Normalizer::new())?;
+    report_skipped(&skipped);
+    let input: Box<dyn BufRead> = if path == "-" {
+        Box::new(io::stdin().lock())
+    } else {
+        let file = fs::File::open(path).map_err(|source| Error::Io {
+            path: path.to_string(),
+            source,
+        })?;
+        Box::new(io::BufReader::new(file))
+    };
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let solve = |board: &Board| analyze(&words, board, &Boggle, None);
+    solve_batch(input, path, threads, solve, |result| match result.outcome {
+        Ok(report) => println!(
+            "{}: {} words, {} points, longest {}",
+            result.id,
+            report.word_count,
+            report.total_score,
+            report.longest_word.as_deref().unwrap_or("-")
+        ),
+        Err(e) => println!("{}: error: {}", result.id, e),
+    })
 }
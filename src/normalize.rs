@@ -0,0 +1,143 @@
+use crate::board::Tile;
+
+// How raw text is cleaned up before it becomes dictionary words or board letters.
+// Line endings are always stripped; case folding is on by default and accent folding is off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Normalizer {
+    fold_case: bool,
+    fold_accents: bool,
+}
+
+impl Default for Normalizer {
+    fn default() -> Normalizer {
+        Normalizer::new()
+    }
+}
+
+impl Normalizer {
+    pub fn new() -> Normalizer {
+        Normalizer {
+            fold_case: true,
+            fold_accents: false,
+        }
+    }
+
+    // Leave upper case letters alone
+    pub fn keep_case(mut self) -> Normalizer {
+        self.fold_case = false;
+        self
+    }
+
+    // Strip accents, e.g. "é" becomes "e" and "ß" becomes "ss"
+    pub fn fold_accents(mut self) -> Normalizer {
+        self.fold_accents = true;
+        self
+    }
+
+    // One line of text with its line ending and surrounding whitespace removed
+    pub fn line<'a>(&self, line: &'a str) -> &'a str {
+        line.trim_end_matches(['\n', '\r']).trim()
+    }
+
+    pub fn word(&self, word: &str) -> String {
+        let mut out = String::with_capacity(word.len());
+        for c in word.chars() {
+            if self.fold_case {
+                for c in c.to_lowercase() {
+                    self.push_letter(&mut out, c);
+                }
+            } else {
+                self.push_letter(&mut out, c);
+            }
+        }
+        out
+    }
+
+    pub fn tile(&self, tile: Tile) -> Tile {
+        match tile {
+            Tile::Letters(letters) => Tile::Letters(self.word(&letters)),
+            tile => tile,
+        }
+    }
+
+    fn push_letter(&self, out: &mut String, c: char) {
+        match self.fold_accents.then(|| unaccented(c)).flatten() {
+            Some(plain) => out.push_str(plain),
+            None => out.push(c),
+        }
+    }
+}
+
+// The accented Latin letters of the Latin-1 Supplement and Latin Extended-A blocks, written
+// without accents. Letters not listed here have no accent to strip.
+fn unaccented(c: char) -> Option<&'static str> {
+    let plain = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'æ' => "ae",
+        'Æ' => "AE",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+        'ď' | 'đ' | 'ð' => "d",
+        'Ď' | 'Đ' | 'Ð' => "D",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+        'ĥ' | 'ħ' => "h",
+        'Ĥ' | 'Ħ' => "H",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+        'ĵ' => "j",
+        'Ĵ' => "J",
+        'ķ' => "k",
+        'Ķ' => "K",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł' => "L",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'Ñ' | 'Ń' | 'Ņ' | 'Ň' => "N",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+        'œ' => "oe",
+        'Œ' => "OE",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'Ŕ' | 'Ŗ' | 'Ř' => "R",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' => "S",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'Ţ' | 'Ť' | 'Ŧ' => "T",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+        'ŵ' => "w",
+        'Ŵ' => "W",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'Ý' | 'Ÿ' | 'Ŷ' => "Y",
+        'ź' | 'ż' | 'ž' => "z",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        _ => return None,
+    };
+    Some(plain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folding() {
+        let norm = Normalizer::new();
+        assert_eq!(norm.line("  Café\r\n"), "Café");
+        assert_eq!(norm.word("Café"), "café");
+        assert_eq!(norm.word("ÉCOLE"), "école");
+        assert_eq!(norm.fold_accents().word("Café"), "cafe");
+        assert_eq!(norm.fold_accents().word("Straße"), "strasse");
+        assert_eq!(norm.keep_case().word("Café"), "Café");
+        assert_eq!(norm.keep_case().fold_accents().word("ÉCOLE"), "ECOLE");
+        assert_eq!(
+            norm.fold_accents().tile(Tile::Letters("Ñ".to_string())),
+            Tile::Letters("n".to_string())
+        );
+        assert_eq!(norm.tile(Tile::Wildcard), Tile::Wildcard);
+    }
+}
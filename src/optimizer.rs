@@ -0,0 +1,305 @@
+use std::time::{Duration, Instant};
+
+use crate::board::{Board, BoardError, Tile, MAX_SIDE};
+use crate::dice::{DiceSet, Rng};
+use crate::error::Error;
+use crate::scoring::ScoringRule;
+use crate::solver::find_all;
+use crate::word_dict::Trie;
+
+// How long to keep searching. An iteration budget gives the same board for the same seed
+// every time; a time budget depends on how many iterations the machine gets through.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Budget {
+    Iterations(usize),
+    Time(Duration),
+}
+
+// Where the optimizer may take letters from
+#[derive(Clone, Copy, Debug)]
+pub enum Letters<'d> {
+    // Any letter on any cell of a size x size board, size from 1 to board::MAX_SIDE
+    Any(usize),
+    // Only arrangements and rolls of these dice
+    Dice(&'d DiceSet),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Optimized {
+    pub board: Board,
+    pub score: u32,
+    pub word_count: usize,
+    pub iterations: usize,
+}
+
+// Letters drawn for unconstrained boards, in roughly the proportions of a Scrabble bag
+const LETTER_BAG: &str = concat!(
+    "aaaaaaaaabbccddddeeeeeeeeeeeeffggghhiiiiiiiiijkllll",
+    "mmnnnnnnooooooooppqrrrrrrssssttttttuuuuvvwwxyyz",
+);
+
+// A board as the optimizer sees it: for each cell, which die sits there and which face is up.
+// Without dice every cell is its own die holding the whole letter bag.
+struct Layout {
+    size: usize,
+    dice: Vec<Vec<Tile>>,
+    cells: Vec<(usize, usize)>,
+}
+
+impl Layout {
+    fn random(letters: &Letters, rng: &mut Rng) -> Layout {
+        let (size, dice) = match letters {
+            Letters::Any(size) => {
+                let bag: Vec<Tile> = LETTER_BAG.chars().map(Tile::from).collect();
+                (*size, vec![bag; size * size])
+            }
+            Letters::Dice(dice) => (dice.size(), dice.dice().to_vec()),
+        };
+        let mut order: Vec<usize> = (0..dice.len()).collect();
+        rng.shuffle(&mut order);
+        let cells = order
+            .into_iter()
+            .map(|die| (die, rng.below(dice[die].len())))
+            .collect();
+        Layout { size, dice, cells }
+    }
+
+    fn board(&self) -> Result<Board, Error> {
+        let rows = self
+            .cells
+            .chunks(self.size.max(1))
+            .map(|row| {
+                row.iter()
+                    .map(|&(die, face)| self.dice[die][face].clone())
+                    .collect()
+            })
+            .collect();
+        Ok(Board::new(rows)?)
+    }
+
+    // Either turn one die to a random face or swap two dice, returning how to undo it
+    fn mutate(&mut self, rng: &mut Rng) -> Vec<(usize, (usize, usize))> {
+        let a = rng.below(self.cells.len());
+        if rng.below(2) == 0 {
+            let undo = vec![(a, self.cells[a])];
+            let die = self.cells[a].0;
+            self.cells[a].1 = rng.below(self.dice[die].len());
+            undo
+        } else {
+            let b = rng.below(self.cells.len());
+            let undo = vec![(a, self.cells[a]), (b, self.cells[b])];
+            self.cells.swap(a, b);
+            undo
+        }
+    }
+
+    fn undo(&mut self, undo: Vec<(usize, (usize, usize))>) {
+        for (cell, was) in undo.into_iter().rev() {
+            self.cells[cell] = was;
+        }
+    }
+}
+
+// Search for the highest scoring board by simulated annealing. Each step rerolls one cell or
+// swaps two; better boards are always kept and worse ones sometimes, less often as the
+// budget runs out.
+pub fn optimize(
+    words: &impl Trie,
+    rule: &impl ScoringRule,
+    letters: &Letters,
+    budget: Budget,
+    seed: u64,
+) -> Result<Optimized, Error> {
+    match letters {
+        Letters::Any(0) => return Err(BoardError::Empty.into()),
+        Letters::Any(size) if *size > MAX_SIDE => {
+            return Err(BoardError::TooLarge {
+                line: MAX_SIDE + 1,
+                column: 1,
+            }
+            .into())
+        }
+        _ => {}
+    }
+    let mut rng = Rng::new(seed);
+    let mut layout = Layout::random(letters, &mut rng);
+    let (mut score, mut word_count) = evaluate(words, rule, &layout.board()?)?;
+    let mut best = Optimized {
+        board: layout.board()?,
+        score,
+        word_count,
+        iterations: 0,
+    };
+    // Scaled to the starting board so rules with big point values still anneal
+    let start_temperature = f64::from(score.max(20)) * 0.05;
+    let start = Instant::now();
+    let mut iterations = 0;
+    loop {
+        // Checked before dividing, so an empty budget doesn't make progress NaN
+        let elapsed = start.elapsed();
+        let progress = match budget {
+            Budget::Iterations(n) if iterations < n => iterations as f64 / n as f64,
+            Budget::Time(limit) if elapsed < limit => elapsed.as_secs_f64() / limit.as_secs_f64(),
+            _ => break,
+        };
+        iterations += 1;
+        let undo = layout.mutate(&mut rng);
+        let board = layout.board()?;
+        let (new_score, new_count) = evaluate(words, rule, &board)?;
+        let temperature = start_temperature * (1.0 - progress);
+        let delta = f64::from(new_score) - f64::from(score);
+        if delta >= 0.0 || rng.next_f64() < (delta / temperature).exp() {
+            score = new_score;
+            word_count = new_count;
+            if score > best.score {
+                best.board = board;
+                best.score = score;
+                best.word_count = word_count;
+            }
+        } else {
+            layout.undo(undo);
+        }
+    }
+    best.iterations = iterations;
+    Ok(best)
+}
+
+// Total score and number of distinct words on the board
+fn evaluate(
+    words: &impl Trie,
+    rule: &impl ScoringRule,
+    board: &Board,
+) -> Result<(u32, usize), Error> {
+    let found = find_all(words, board)?;
+    let score = found
+        .iter()
+        .map(|(word, path)| rule.score(word, path))
+        .sum();
+    Ok((score, found.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::Boggle;
+    use crate::solver::total_score;
+    use crate::word_dict::hashmap::TrieHashMap;
+
+    fn dict() -> TrieHashMap {
+        TrieHashMap::from_words(&[
+            "ate", "eat", "tea", "eta", "tae", "sat", "set", "seat", "east", "eats", "teas",
+            "sate", "etas", "tease", "rat", "tar", "art", "star", "rats", "tars", "rate", "tear",
+            "stare", "tears", "rates", "aster",
+        ])
+    }
+
+    #[test]
+    fn reproducible() {
+        let words = dict();
+        let run = |seed| {
+            optimize(
+                &words,
+                &Boggle,
+                &Letters::Any(3),
+                Budget::Iterations(200),
+                seed,
+            )
+            .unwrap()
+        };
+        let best = run(5);
+        assert_eq!(best, run(5));
+        assert_eq!(best.iterations, 200);
+        assert_eq!(
+            best.score,
+            total_score(&words, &best.board, &Boggle).unwrap()
+        );
+        assert_eq!(
+            best.word_count,
+            find_all(&words, &best.board).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn improves_on_start() {
+        let words = dict();
+        let start = optimize(&words, &Boggle, &Letters::Any(3), Budget::Iterations(0), 9).unwrap();
+        assert_eq!(start.iterations, 0);
+        assert_eq!(
+            start.score,
+            total_score(&words, &start.board, &Boggle).unwrap()
+        );
+        let best = optimize(
+            &words,
+            &Boggle,
+            &Letters::Any(3),
+            Budget::Iterations(500),
+            9,
+        )
+        .unwrap();
+        assert!(best.score > start.score);
+    }
+
+    #[test]
+    fn empty_budget() {
+        let words = dict();
+        let time = optimize(
+            &words,
+            &Boggle,
+            &Letters::Any(3),
+            Budget::Time(Duration::ZERO),
+            9,
+        );
+        assert_eq!(time.unwrap().iterations, 0);
+    }
+
+    #[test]
+    fn bad_sizes() {
+        let words = dict();
+        let run = |size| {
+            optimize(
+                &words,
+                &Boggle,
+                &Letters::Any(size),
+                Budget::Iterations(10),
+                1,
+            )
+        };
+        assert!(matches!(run(0), Err(Error::Board(BoardError::Empty))));
+        assert!(matches!(
+            run(MAX_SIDE + 1),
+            Err(Error::Board(BoardError::TooLarge { .. }))
+        ));
+    }
+
+    #[test]
+    fn stays_on_dice() {
+        let words = dict();
+        let dice = DiceSet::classic();
+        let best = optimize(
+            &words,
+            &Boggle,
+            &Letters::Dice(&dice),
+            Budget::Iterations(100),
+            1,
+        )
+        .unwrap();
+        assert_eq!(best.board.height(), 4);
+        for tile in best.board.rows().iter().flatten() {
+            assert!(dice.dice().iter().any(|die| die.contains(tile)));
+        }
+    }
+
+    #[test]
+    fn time_budget() {
+        let words = dict();
+        let best = optimize(
+            &words,
+            &Boggle,
+            &Letters::Any(3),
+            Budget::Time(Duration::from_millis(50)),
+            3,
+        )
+        .unwrap();
+        assert!(best.iterations > 0);
+    }
+}
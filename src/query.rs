@@ -0,0 +1,167 @@
+// Which words a solve should return. Every filter is checked against each prefix while
+// the board is searched, so paths that can't lead to an allowed word are cut short.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolveQuery {
+    min_len: usize,
+    max_len: Option<usize>,
+    required: Vec<char>,
+    forbidden: Vec<char>,
+    pattern: Option<Vec<char>>,
+}
+
+impl SolveQuery {
+    // Allows every word
+    pub fn new() -> SolveQuery {
+        SolveQuery::default()
+    }
+
+    pub fn min_len(self, min_len: usize) -> SolveQuery {
+        SolveQuery { min_len, ..self }
+    }
+
+    pub fn max_len(self, max_len: usize) -> SolveQuery {
+        SolveQuery {
+            max_len: Some(max_len),
+            ..self
+        }
+    }
+
+    // Words must contain each of these letters at least once
+    pub fn requiring(mut self, letters: &str) -> SolveQuery {
+        for c in letters.chars() {
+            if !self.required.contains(&c) {
+                self.required.push(c);
+            }
+        }
+        self
+    }
+
+    // Words must not contain any of these letters
+    pub fn forbidding(mut self, letters: &str) -> SolveQuery {
+        self.forbidden.extend(letters.chars());
+        self
+    }
+
+    // Words must match a glob where '?' is any one letter and '*' is any run of letters,
+    // e.g. "s??e*"
+    pub fn matching(self, pattern: &str) -> SolveQuery {
+        SolveQuery {
+            pattern: Some(pattern.chars().collect()),
+            ..self
+        }
+    }
+
+    // Whether some word starting with prefix could still be allowed
+    pub fn allows_prefix(&self, prefix: &str) -> bool {
+        if prefix.chars().any(|c| self.forbidden.contains(&c)) {
+            return false;
+        }
+        if let Some(max_len) = self.max_len {
+            let len = prefix.chars().count();
+            let missing = self
+                .required
+                .iter()
+                .filter(|c| !prefix.contains(**c))
+                .count();
+            if len + missing > max_len {
+                return false;
+            }
+        }
+        match &self.pattern {
+            Some(pattern) => !glob_states(pattern, prefix).is_empty(),
+            None => true,
+        }
+    }
+
+    pub fn matches(&self, word: &str) -> bool {
+        let len = word.chars().count();
+        len >= self.min_len
+            && self.max_len.is_none_or(|max_len| len <= max_len)
+            && self.required.iter().all(|c| word.contains(*c))
+            && !word.chars().any(|c| self.forbidden.contains(&c))
+            && self
+                .pattern
+                .as_ref()
+                .is_none_or(|pattern| glob_states(pattern, word).contains(&pattern.len()))
+    }
+}
+
+// Positions in pattern that text could have brought us to. Empty if text can't be the
+// start of a match; contains pattern.len() if text matches the whole pattern.
+fn glob_states(pattern: &[char], text: &str) -> Vec<usize> {
+    let mut states = glob_closure(pattern, vec![0]);
+    for c in text.chars() {
+        let next = states
+            .iter()
+            .filter_map(|&i| match pattern.get(i) {
+                Some('*') => Some(i),
+                Some('?') => Some(i + 1),
+                Some(p) if *p == c => Some(i + 1),
+                _ => None,
+            })
+            .collect();
+        states = glob_closure(pattern, next);
+        if states.is_empty() {
+            break;
+        }
+    }
+    states
+}
+
+// A '*' can match nothing, so reaching one also reaches the position after it
+fn glob_closure(pattern: &[char], mut states: Vec<usize>) -> Vec<usize> {
+    let mut i = 0;
+    while i < states.len() {
+        let state = states[i];
+        if pattern.get(state) == Some(&'*') && !states.contains(&(state + 1)) {
+            states.push(state + 1);
+        }
+        i += 1;
+    }
+    states.sort_unstable();
+    states.dedup();
+    states
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn globs() {
+        let query = SolveQuery::new().matching("s??e*");
+        assert!(query.matches("same"));
+        assert!(query.matches("sneer"));
+        assert!(!query.matches("sam"));
+        assert!(!query.matches("stare"));
+        assert!(query.allows_prefix("sn"));
+        assert!(!query.allows_prefix("t"));
+        assert!(query.allows_prefix("sna"));
+        assert!(!query.allows_prefix("snap"));
+
+        let query = SolveQuery::new().matching("*ing");
+        assert!(query.matches("sing"));
+        assert!(query.matches("ing"));
+        assert!(!query.matches("singe"));
+        assert!(query.allows_prefix("xyz"));
+    }
+
+    #[test]
+    fn letters_and_lengths() {
+        let query = SolveQuery::new()
+            .min_len(4)
+            .max_len(5)
+            .requiring("z")
+            .forbidding("q");
+        assert!(query.matches("zone"));
+        assert!(!query.matches("zoo"));
+        assert!(!query.matches("zonked"));
+        assert!(!query.matches("bone"));
+        assert!(!query.matches("quiz"));
+        assert!(query.allows_prefix("bon"));
+        assert!(query.allows_prefix("bone"));
+        assert!(!query.allows_prefix("boned"));
+        assert!(!query.allows_prefix("qu"));
+        assert!(SolveQuery::new().matches("a"));
+    }
+}
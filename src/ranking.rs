@@ -0,0 +1,175 @@
+use crate::board::Pos;
+use crate::error::Error;
+use crate::grid::Grid;
+use crate::scoring::{average_letter_value, ScoringRule};
+use crate::solver::{find_all, find_all_best_paths};
+use crate::word_dict::Trie;
+
+// What makes one word better than another. Higher values rank first, except for Rarity.
+pub enum Ranking<'r, P = Pos> {
+    // Letters in the word
+    Length,
+    // The rule's score for the first path found that spells the word
+    Score(&'r dyn ScoringRule<P>),
+    // The rule's score for the word's highest scoring path. Only differs from Score for rules
+    // that look at the path, like scoring::Ruzzle, and costs more since every path is tried.
+    PathScore(&'r dyn ScoringRule<P>),
+    // How often the word turns up, from a caller-supplied word frequency source such as a
+    // lookup into a frequency list. The least frequent words rank first.
+    Rarity(&'r dyn Fn(&str) -> u32),
+    // Average Scrabble value of the word's letters, see scoring::average_letter_value
+    LetterValue,
+}
+
+impl<P> Ranking<'_, P> {
+    pub fn value(&self, word: &str, path: &[P]) -> u32 {
+        match self {
+            Ranking::Length => word.chars().count() as u32,
+            Ranking::Score(rule) | Ranking::PathScore(rule) => rule.score(word, path),
+            Ranking::Rarity(frequency) => frequency(word),
+            Ranking::LetterValue => average_letter_value(word),
+        }
+    }
+}
+
+// A word, the cells that spell it and its value
+pub type Ranked<P> = (String, Vec<P>, u32);
+
+// Every distinct word in the board with its value, best first.
+// Ties are broken alphabetically, so the order never depends on where words sit on the board.
+pub fn rank<G: Grid>(
+    words: &impl Trie,
+    grid: &G,
+    ranking: &Ranking<G::Pos>,
+) -> Result<Vec<Ranked<G::Pos>>, Error> {
+    let mut ranked: Vec<Ranked<G::Pos>> = match ranking {
+        Ranking::PathScore(rule) => find_all_best_paths(words, grid, *rule)?,
+        _ => find_all(words, grid)?
+            .into_iter()
+            .map(|(word, path)| {
+                let value = ranking.value(&word, &path);
+                (word, path, value)
+            })
+            .collect(),
+    };
+    // Both solves are already alphabetical, so a stable sort keeps ties in order
+    match ranking {
+        Ranking::Rarity(_) => ranked.sort_by_key(|w| w.2),
+        _ => ranked.sort_by_key(|w| std::cmp::Reverse(w.2)),
+    }
+    Ok(ranked)
+}
+
+// The k best words in the board
+pub fn top_k<G: Grid>(
+    words: &impl Trie,
+    grid: &G,
+    ranking: &Ranking<G::Pos>,
+    k: usize,
+) -> Result<Vec<Ranked<G::Pos>>, Error> {
+    let mut ranked = rank(words, grid, ranking)?;
+    ranked.truncate(k);
+    Ok(ranked)
+}
+
+// Every word tied for first place, alphabetically
+pub fn best_ties<G: Grid>(
+    words: &impl Trie,
+    grid: &G,
+    ranking: &Ranking<G::Pos>,
+) -> Result<Vec<Ranked<G::Pos>>, Error> {
+    let mut ranked = rank(words, grid, ranking)?;
+    if let Some(best) = ranked.first().map(|w| w.2) {
+        ranked.retain(|w| w.2 == best);
+    }
+    Ok(ranked)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::board::Board;
+    use crate::scoring::Boggle;
+    use crate::word_dict::hashmap::TrieHashMap;
+
+    fn ranked_words<P>(ranked: &[(String, Vec<P>, u32)]) -> Vec<(&str, u32)> {
+        ranked.iter().map(|(w, _, v)| (w.as_str(), *v)).collect()
+    }
+
+    #[test]
+    fn rankings() {
+        // o n e
+        // z a t
+        let words =
+            TrieHashMap::from_words(&["one", "neat", "ant", "tan", "zoa", "eta", "tea", "oat"]);
+        let board = Board::parse("one\nzat").unwrap();
+        assert_eq!(
+            ranked_words(&top_k(&words, &board, &Ranking::Length, 3).unwrap()),
+            vec![("neat", 4), ("ant", 3), ("eta", 3)]
+        );
+        assert_eq!(
+            ranked_words(&top_k(&words, &board, &Ranking::Score(&Boggle), 2).unwrap()),
+            vec![("ant", 1), ("eta", 1)]
+        );
+        assert_eq!(
+            ranked_words(&top_k(&words, &board, &Ranking::LetterValue, 3).unwrap()),
+            vec![("zoa", 40), ("ant", 10), ("eta", 10)]
+        );
+        let frequencies: HashMap<&str, u32> = [
+            ("one", 900),
+            ("neat", 20),
+            ("ant", 40),
+            ("tan", 35),
+            ("eta", 1),
+            ("tea", 60),
+            ("oat", 8),
+        ]
+        .into_iter()
+        .collect();
+        let frequency = |word: &str| frequencies.get(word).copied().unwrap_or(0);
+        assert_eq!(
+            ranked_words(&top_k(&words, &board, &Ranking::Rarity(&frequency), 3).unwrap()),
+            vec![("zoa", 0), ("eta", 1), ("oat", 8)]
+        );
+        assert_eq!(
+            top_k(&words, &board, &Ranking::Length, 100).unwrap().len(),
+            8
+        );
+    }
+
+    #[test]
+    fn ties_for_first() {
+        let words =
+            TrieHashMap::from_words(&["one", "neat", "ant", "tan", "zoa", "eta", "tea", "oat"]);
+        let board = Board::parse("one\nzat").unwrap();
+        assert_eq!(
+            ranked_words(&best_ties(&words, &board, &Ranking::Length).unwrap()),
+            vec![("neat", 4)]
+        );
+        assert_eq!(
+            ranked_words(&best_ties(&words, &board, &Ranking::Score(&Boggle)).unwrap()),
+            vec![
+                ("ant", 1),
+                ("eta", 1),
+                ("neat", 1),
+                ("oat", 1),
+                ("one", 1),
+                ("tan", 1),
+                ("tea", 1),
+                ("zoa", 1),
+            ]
+        );
+        let frequency = |word: &str| match word {
+            "tan" | "oat" => 1,
+            _ => 50,
+        };
+        assert_eq!(
+            ranked_words(&best_ties(&words, &board, &Ranking::Rarity(&frequency)).unwrap()),
+            vec![("oat", 1), ("tan", 1)]
+        );
+        let empty = Board::parse("xx").unwrap();
+        assert_eq!(best_ties(&words, &empty, &Ranking::Length).unwrap(), vec![]);
+    }
+}
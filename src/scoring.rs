@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+
+use crate::board::{Board, BoardError, Pos, Tile};
+
+// Maps a found word (and the path that spells it) to points
+pub trait ScoringRule<P = Pos> {
+    fn score(&self, word: &str, path: &[P]) -> u32;
+}
+
+// Classic 4x4 Boggle: 3-4 letters = 1, 5 = 2, 6 = 3, 7 = 5, 8+ = 11
+pub struct Boggle;
+
+// Big Boggle (5x5): same table as classic, but words need at least 4 letters
+pub struct BigBoggle;
+
+// GamePigeon Word Hunt: 3 letters = 100, 4 = 400, 5 = 800, 6 = 1400,
+// then +400 for every letter after that
+pub struct WordHunt;
+
+// The average Scrabble value of a word's letters, times ten. This only measures how unusual
+// the letters are, not how rare the word itself is: "zoa" scores far above "the" either way.
+pub fn average_letter_value(word: &str) -> u32 {
+    let len = word.chars().count() as u32;
+    if len == 0 {
+        return 0;
+    }
+    word.chars().map(letter_value).sum::<u32>() * 10 / len
+}
+
+fn letter_value(c: char) -> u32 {
+    match c.to_ascii_lowercase() {
+        'd' | 'g' => 2,
+        'b' | 'c' | 'm' | 'p' => 3,
+        'f' | 'h' | 'v' | 'w' | 'y' => 4,
+        'k' => 5,
+        'j' | 'x' => 8,
+        'q' | 'z' => 10,
+        _ => 1,
+    }
+}
+
+fn boggle_points(len: usize) -> u32 {
+    match len {
+        0..=2 => 0,
+        3 | 4 => 1,
+        5 => 2,
+        6 => 3,
+        7 => 5,
+        _ => 11,
+    }
+}
+
+impl<P> ScoringRule<P> for Boggle {
+    fn score(&self, word: &str, _path: &[P]) -> u32 {
+        boggle_points(word.chars().count())
+    }
+}
+
+impl<P> ScoringRule<P> for BigBoggle {
+    fn score(&self, word: &str, _path: &[P]) -> u32 {
+        match word.chars().count() {
+            0..=3 => 0,
+            len => boggle_points(len),
+        }
+    }
+}
+
+impl<P> ScoringRule<P> for WordHunt {
+    fn score(&self, word: &str, _path: &[P]) -> u32 {
+        match word.chars().count() {
+            0..=2 => 0,
+            3 => 100,
+            4 => 400,
+            5 => 800,
+            len => 1400 + 400 * (len as u32 - 6),
+        }
+    }
+}
+
+// A Ruzzle / Wordament bonus square
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Modifier {
+    #[default]
+    Plain,
+    DoubleLetter,
+    TripleLetter,
+    DoubleWord,
+    TripleWord,
+}
+
+// Ruzzle-style scoring for one board: each cell is worth the points of its letters, letter
+// bonuses multiply a cell, word bonuses multiply the whole word, and the word scores the sum
+// along its path. Unlike the other rules, two paths for the same word can score differently.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ruzzle {
+    cells: Vec<Vec<(u32, Modifier)>>,
+}
+
+impl Ruzzle {
+    // Letters are worth their Scrabble values; wildcards and blanks are worth nothing
+    pub fn new(board: &Board) -> Ruzzle {
+        Ruzzle::from_values(board, letter_value)
+    }
+
+    // Letters are worth their points in the table, e.g. a language's Ruzzle tile values.
+    // Letters missing from the table, wildcards and blanks are worth nothing.
+    pub fn with_points(board: &Board, points: &HashMap<char, u32>) -> Ruzzle {
+        Ruzzle::from_values(board, |c| points.get(&c).copied().unwrap_or(0))
+    }
+
+    fn from_values(board: &Board, value: impl Fn(char) -> u32) -> Ruzzle {
+        let cells = board
+            .rows()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|tile| (tile_value(tile, &value), Modifier::Plain))
+                    .collect()
+            })
+            .collect();
+        Ruzzle { cells }
+    }
+
+    // Scrabble values with bonus squares, see modifiers
+    pub fn with_modifiers(board: &Board, layout: &str) -> Result<Ruzzle, BoardError> {
+        Ruzzle::new(board).modifiers(layout)
+    }
+
+    // Bonus squares laid out like the board, one whitespace separated code per cell:
+    // DL, TL, DW, TW, or '.' for a plain cell. Codes are case insensitive.
+    //
+    //     . DL .  .
+    //     . .  TW .
+    pub fn modifiers(self, layout: &str) -> Result<Ruzzle, BoardError> {
+        let mut ruzzle = self;
+        let mut row = 0;
+        for (i, line) in layout.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let cells = ruzzle.cells.get_mut(row).ok_or(BoardError::TooLarge {
+                line: i + 1,
+                column: 1,
+            })?;
+            let mut found = 0;
+            for (column, code) in tokens(line) {
+                let modifier = match code.to_ascii_uppercase().as_str() {
+                    "." => Modifier::Plain,
+                    "DL" => Modifier::DoubleLetter,
+                    "TL" => Modifier::TripleLetter,
+                    "DW" => Modifier::DoubleWord,
+                    "TW" => Modifier::TripleWord,
+                    _ => {
+                        return Err(BoardError::Unsupported {
+                            line: i + 1,
+                            column,
+                            found: code.chars().next().unwrap(),
+                        })
+                    }
+                };
+                if let Some(cell) = cells.get_mut(found) {
+                    cell.1 = modifier;
+                }
+                found += 1;
+            }
+            if found != cells.len() {
+                return Err(BoardError::Ragged {
+                    line: i + 1,
+                    expected: cells.len(),
+                    found,
+                });
+            }
+            row += 1;
+        }
+        if row < ruzzle.cells.len() {
+            return Err(BoardError::Ragged {
+                line: layout.lines().count() + 1,
+                expected: ruzzle.cells[0].len(),
+                found: 0,
+            });
+        }
+        Ok(ruzzle)
+    }
+
+    // Points and bonus of one cell
+    pub fn cell(&self, pos: Pos) -> (u32, Modifier) {
+        self.cells[pos.0][pos.1]
+    }
+
+    // Make one cell worth the given points before bonuses, whatever its letters
+    pub fn set_points(&mut self, pos: Pos, points: u32) {
+        self.cells[pos.0][pos.1].0 = points;
+    }
+}
+
+impl ScoringRule<Pos> for Ruzzle {
+    fn score(&self, _word: &str, path: &[Pos]) -> u32 {
+        let mut sum = 0;
+        let mut multiplier = 1;
+        for &pos in path {
+            let (value, modifier) = self.cell(pos);
+            sum += match modifier {
+                Modifier::DoubleLetter => value * 2,
+                Modifier::TripleLetter => value * 3,
+                _ => value,
+            };
+            multiplier *= match modifier {
+                Modifier::DoubleWord => 2,
+                Modifier::TripleWord => 3,
+                _ => 1,
+            };
+        }
+        sum * multiplier
+    }
+}
+
+fn tile_value(tile: &Tile, value: impl Fn(char) -> u32) -> u32 {
+    match tile {
+        Tile::Letters(letters) => letters.chars().map(value).sum(),
+        Tile::Wildcard | Tile::Blank => 0,
+    }
+}
+
+// Each whitespace separated token in line with the char column (from 1) it starts at
+fn tokens(line: &str) -> Vec<(usize, String)> {
+    let mut tokens: Vec<(usize, String)> = Vec::new();
+    let mut in_token = false;
+    for (i, c) in line.chars().enumerate() {
+        if c.is_whitespace() {
+            in_token = false;
+        } else if in_token {
+            tokens.last_mut().unwrap().1.push(c);
+        } else {
+            tokens.push((i + 1, c.to_string()));
+            in_token = true;
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scores(rule: &impl ScoringRule) -> Vec<u32> {
+        ["ab", "abc", "abcd", "abcde", "abcdef", "abcdefg", "abcdefgh", "abcdefghi"]
+            .iter()
+            .map(|w| rule.score(w, &[]))
+            .collect()
+    }
+
+    #[test]
+    fn presets() {
+        assert_eq!(scores(&Boggle), vec![0, 1, 1, 2, 3, 5, 11, 11]);
+        assert_eq!(scores(&BigBoggle), vec![0, 0, 1, 2, 3, 5, 11, 11]);
+        assert_eq!(
+            scores(&WordHunt),
+            vec![0, 100, 400, 800, 1400, 1800, 2200, 2600]
+        );
+    }
+
+    #[test]
+    fn ruzzle_bonuses() {
+        let board = Board::parse("ca\nto").unwrap();
+        let plain = Ruzzle::new(&board);
+        assert_eq!(plain.score("cat", &[(0, 0), (0, 1), (1, 0)]), 5);
+
+        let ruzzle = Ruzzle::with_modifiers(&board, "dl .\n. TW\n").unwrap();
+        assert_eq!(ruzzle.cell((0, 0)), (3, Modifier::DoubleLetter));
+        assert_eq!(ruzzle.score("cat", &[(0, 0), (0, 1), (1, 0)]), 8);
+        assert_eq!(ruzzle.score("coat", &[(0, 0), (1, 1), (0, 1), (1, 0)]), 27);
+
+        let wild = Board::parse("?[qu]").unwrap();
+        assert_eq!(Ruzzle::new(&wild).score("aqu", &[(0, 0), (0, 1)]), 11);
+    }
+
+    #[test]
+    fn ruzzle_point_tables() {
+        let board = Board::parse("ca\nto").unwrap();
+        let points = HashMap::from([('c', 4), ('a', 1), ('t', 1)]);
+        let mut ruzzle = Ruzzle::with_points(&board, &points)
+            .modifiers("TL .\n. .")
+            .unwrap();
+        assert_eq!(ruzzle.cell((1, 1)), (0, Modifier::Plain));
+        assert_eq!(ruzzle.score("cat", &[(0, 0), (0, 1), (1, 0)]), 14);
+        ruzzle.set_points((0, 1), 5);
+        assert_eq!(ruzzle.score("cat", &[(0, 0), (0, 1), (1, 0)]), 18);
+    }
+
+    #[test]
+    fn ruzzle_layout_errors() {
+        let board = Board::parse("ca\nto").unwrap();
+        let layout = |text| Ruzzle::with_modifiers(&board, text).unwrap_err();
+        assert_eq!(
+            layout(". XL\n. ."),
+            BoardError::Unsupported {
+                line: 1,
+                column: 3,
+                found: 'X'
+            }
+        );
+        assert_eq!(
+            layout(". .\n. . DW"),
+            BoardError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 3
+            }
+        );
+        assert_eq!(
+            layout(". ."),
+            BoardError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 0
+            }
+        );
+        assert_eq!(
+            layout(". .\n. .\n. ."),
+            BoardError::TooLarge { line: 3, column: 1 }
+        );
+    }
+
+    #[test]
+    fn letter_values() {
+        assert_eq!(average_letter_value("the"), 20);
+        assert_eq!(average_letter_value("jazz"), 72);
+        assert_eq!(average_letter_value(""), 0);
+    }
+}
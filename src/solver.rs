@@ -1,114 +1,596 @@
-use crate::{word_dict::Trie, Board, Pos};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::ops::Range;
+use std::thread;
+
+use crate::adjacency::Adjacency;
+use crate::board::{Board, Pos, Tile, MAX_SIDE};
+use crate::error::Error;
+use crate::grid::square::SquareGrid;
+use crate::grid::Grid;
+use crate::query::SolveQuery;
+use crate::ranking::{best_ties, rank, Ranked, Ranking};
+use crate::scoring::ScoringRule;
+use crate::word_dict::Trie;
+
+// Most cells any grid may have. A validated Board never goes over this, but hex and other
+// grids aren't bounded by MAX_SIDE.
+pub const MAX_CELLS: usize = MAX_SIDE * MAX_SIDE;
+
+// A word and the cells that spell it
+pub type WordPath<P> = (String, Vec<P>);
+// A word, one path that spells it and how many paths do
+pub type PathCount<P> = (String, Vec<P>, usize);
+// Every word with one path that spells it, and every cell on a path of any word
+pub type WordsAndCells<P> = (Vec<WordPath<P>>, Vec<P>);
 
 // Find longest word in the board
-pub fn find_best(words: &impl Trie, board: &Board) -> (String, Vec<Pos>) {
+pub fn find_best<G: Grid>(words: &impl Trie, grid: &G) -> Result<(String, Vec<G::Pos>), Error> {
+    let layout = Layout::new(grid)?;
+    Ok(find_best_from(words, &layout, 0..layout.len()))
+}
+
+// Find longest word in the board, moving between cells by the given adjacency
+pub fn find_best_with(
+    words: &impl Trie,
+    board: &Board,
+    adjacency: &Adjacency,
+) -> Result<WordPath<Pos>, Error> {
+    find_best(words, &SquareGrid::new(board, adjacency))
+}
+
+// Find longest word in the board, splitting the start cells across threads.
+// Gives the same answer as find_best. The dictionary has to be Sync, which rules out
+// TrieLinkedListArena.
+pub fn find_best_parallel<G>(
+    words: &(impl Trie + Sync),
+    grid: &G,
+    threads: usize,
+) -> Result<(String, Vec<G::Pos>), Error>
+where
+    G: Grid + Sync,
+    G::Pos: Send + Sync,
+{
+    let layout = Layout::new(grid)?;
     let mut best = ("".to_string(), Vec::new());
-    let height = board.len();
-    let width = board[0].len();
-    for row in 0..height {
-        for col in 0..width {
-            let word = find_best_acc(
-                words,
-                board,
-                (row, col),
-                &mut "".to_string(),
-                &mut Vec::new(),
-            );
-            if let Some(w) = word {
-                if w.1.len() > best.1.len() {
-                    best = w;
-                }
+    // Chunks are merged in start order, so ties resolve the same way as a serial solve
+    for w in split_starts(layout.len(), threads, |starts| {
+        find_best_from(words, &layout, starts)
+    }) {
+        if w.0.chars().count() > best.0.chars().count() {
+            best = w;
+        }
+    }
+    Ok(best)
+}
+
+// Find longest word starting from any of the given cells
+fn find_best_from<T: Trie, P: Copy>(
+    words: &T,
+    layout: &Layout<P>,
+    starts: Range<usize>,
+) -> (String, Vec<P>) {
+    let mut search = Search::new(layout.len());
+    let mut best = ("".to_string(), Vec::new());
+    let mut best_len = 0;
+    for start in starts {
+        // Within one start cell the last longest word found wins, across start cells the first
+        let mut start_best: Option<(String, Vec<usize>)> = None;
+        let mut start_len = 0;
+        search.walk(words, layout, start, &|_| true, &mut |word, path| {
+            let len = word.chars().count();
+            if len >= start_len {
+                start_best = Some((word.to_string(), path.to_vec()));
+                start_len = len;
+            }
+        });
+        if let Some((word, path)) = start_best {
+            if start_len > best_len {
+                best = (word, layout.path(&path));
+                best_len = start_len;
             }
         }
     }
     best
 }
 
-// Find longest word in the board if we've already taken the given path to build the given string
-fn find_best_acc(
+// Find every distinct word in the board, each with the first path found that spells it.
+// Results are sorted alphabetically.
+pub fn find_all<G: Grid>(words: &impl Trie, grid: &G) -> Result<Vec<WordPath<G::Pos>>, Error> {
+    find_matching(words, grid, &SolveQuery::new())
+}
+
+// Find every distinct word in the board that the query allows. Paths are abandoned as soon
+// as the query rules them out, so a narrow query is cheaper than filtering find_all.
+pub fn find_matching<G: Grid>(
+    words: &impl Trie,
+    grid: &G,
+    query: &SolveQuery,
+) -> Result<Vec<WordPath<G::Pos>>, Error> {
+    let layout = Layout::new(grid)?;
+    Ok(find_all_from(words, &layout, 0..layout.len(), query)
+        .into_iter()
+        .collect())
+}
+
+// Find every distinct word in the board, moving between cells by the given adjacency
+pub fn find_all_with(
     words: &impl Trie,
     board: &Board,
-    pos: Pos,
-    word_so_far: &mut String,
-    path: &mut Vec<Pos>,
-) -> Option<(String, Vec<Pos>)> {
-    let char_at = board[pos.0][pos.1];
-    if !char_at.is_alphabetic() {
-        return None;
-    }
-    word_so_far.push(char_at);
-    // println!("path: {:?}, word: {:?}", path, word_so_far);
-
-    match words.traverse(&char_at.to_string()) {
-        Some(dict) => {
-            path.push(pos);
-            let best = neighbors(board, pos)
-                .iter()
-                .filter(|p| !path.contains(*p))
-                .filter_map(|p| {
-                    find_best_acc(
-                        dict,
-                        board,
-                        *p,
-                        &mut word_so_far.clone(),
-                        &mut path.clone(),
-                    )
-                })
-                .max_by(|x, y| x.1.len().cmp(&y.1.len()));
-            // println!("word so far: {:} best: {:?}", word_so_far, best);
-            match best {
-                Some(s) => Some(s),
+    adjacency: &Adjacency,
+) -> Result<Vec<WordPath<Pos>>, Error> {
+    find_all(words, &SquareGrid::new(board, adjacency))
+}
+
+// Find every distinct word in the board, splitting the start cells across threads.
+// Gives the same answer as find_all.
+pub fn find_all_parallel<G>(
+    words: &(impl Trie + Sync),
+    grid: &G,
+    threads: usize,
+) -> Result<Vec<WordPath<G::Pos>>, Error>
+where
+    G: Grid + Sync,
+    G::Pos: Send + Sync,
+{
+    let layout = Layout::new(grid)?;
+    let mut found = BTreeMap::new();
+    // Earlier chunks win, so each word keeps the path a serial solve would have found first
+    for chunk in split_starts(layout.len(), threads, |starts| {
+        find_all_from(words, &layout, starts, &SolveQuery::new())
+    }) {
+        for (word, path) in chunk {
+            found.entry(word).or_insert(path);
+        }
+    }
+    Ok(found.into_iter().collect())
+}
+
+// Every word the query allows, starting from any of the given cells
+fn find_all_from<T: Trie, P: Copy>(
+    words: &T,
+    layout: &Layout<P>,
+    starts: Range<usize>,
+    query: &SolveQuery,
+) -> BTreeMap<String, Vec<P>> {
+    let mut search = Search::new(layout.len());
+    let mut found = BTreeMap::new();
+    let viable = |prefix: &str| query.allows_prefix(prefix);
+    for start in starts {
+        search.walk(words, layout, start, &viable, &mut |word, path| {
+            if query.matches(word) && !found.contains_key(word) {
+                found.insert(word.to_string(), layout.path(path));
+            }
+        });
+    }
+    found
+}
+
+// Find every distinct word in the board like find_all, along with every cell that lies on
+// some path of some word, in positions() order. Both come out of one walk of the board.
+pub fn find_all_and_used_cells<G: Grid>(
+    words: &impl Trie,
+    grid: &G,
+) -> Result<WordsAndCells<G::Pos>, Error> {
+    let layout = Layout::new(grid)?;
+    let mut search = Search::new(layout.len());
+    let mut found = BTreeMap::new();
+    let mut used = vec![false; layout.len()];
+    for start in 0..layout.len() {
+        search.walk(words, &layout, start, &|_| true, &mut |word, path| {
+            for &cell in path {
+                used[cell] = true;
+            }
+            if !found.contains_key(word) {
+                found.insert(word.to_string(), layout.path(path));
+            }
+        });
+    }
+    let used = (0..layout.len()).filter(|&c| used[c]).collect::<Vec<_>>();
+    Ok((found.into_iter().collect(), layout.path(&used)))
+}
+
+// Find every distinct word in the board with the first path found that spells it and the
+// number of distinct paths that do. Counts stop at cap, since a long word on a repetitive
+// board can be spelled a combinatorial number of ways.
+pub fn count_paths<G: Grid>(
+    words: &impl Trie,
+    grid: &G,
+    cap: usize,
+) -> Result<Vec<PathCount<G::Pos>>, Error> {
+    let layout = Layout::new(grid)?;
+    let mut search = Search::new(layout.len());
+    let mut found: BTreeMap<String, (Vec<G::Pos>, usize)> = BTreeMap::new();
+    for start in 0..layout.len() {
+        search.walk(words, &layout, start, &|_| true, &mut |word, path| {
+            match found.get_mut(word) {
+                Some((_, count)) => *count = (*count + 1).min(cap),
                 None => {
-                    if dict.is_word() {
-                        Some((word_so_far.to_string(), path.to_vec()))
-                    } else {
-                        None
-                    }
+                    found.insert(word.to_string(), (layout.path(path), 1.min(cap)));
                 }
             }
+        });
+    }
+    Ok(found
+        .into_iter()
+        .map(|(word, (path, count))| (word, path, count))
+        .collect())
+}
+
+// Run solve over contiguous runs of start cells on up to the given number of threads,
+// returning the results in start order
+fn split_starts<R, F>(cells: usize, threads: usize, solve: F) -> Vec<R>
+where
+    R: Send,
+    F: Fn(Range<usize>) -> R + Sync,
+{
+    let chunk_size = cells.div_ceil(threads.max(1)).max(1);
+    thread::scope(|s| {
+        let handles: Vec<_> = (0..cells)
+            .step_by(chunk_size)
+            .map(|start| {
+                let solve = &solve;
+                s.spawn(move || solve(start..cells.min(start + chunk_size)))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("solver thread panicked"))
+            .collect()
+    })
+}
+
+// Total points for every distinct word in the board under the given rule
+pub fn total_score<G: Grid>(
+    words: &impl Trie,
+    grid: &G,
+    rule: &impl ScoringRule<G::Pos>,
+) -> Result<u32, Error> {
+    Ok(find_all(words, grid)?
+        .iter()
+        .map(|(word, path)| rule.score(word, path))
+        .sum())
+}
+
+// Every distinct word in the board with its points, highest scoring first.
+// Words scoring zero are dropped and ties are broken alphabetically.
+pub fn rank_by_score<G: Grid>(
+    words: &impl Trie,
+    grid: &G,
+    rule: &impl ScoringRule<G::Pos>,
+) -> Result<Vec<Ranked<G::Pos>>, Error> {
+    let mut ranked = rank(words, grid, &Ranking::Score(rule))?;
+    ranked.retain(|w| w.2 > 0);
+    Ok(ranked)
+}
+
+// Every distinct word in the board on its highest scoring path under the rule, alphabetically.
+// Every path is tried, so this costs more than find_all and only pays off for rules like
+// scoring::Ruzzle where the path changes the score. Of equally good paths the first is kept.
+pub fn find_all_best_paths<G: Grid>(
+    words: &impl Trie,
+    grid: &G,
+    rule: &(impl ScoringRule<G::Pos> + ?Sized),
+) -> Result<Vec<Ranked<G::Pos>>, Error> {
+    let layout = Layout::new(grid)?;
+    let mut search = Search::new(layout.len());
+    let mut found: BTreeMap<String, (Vec<G::Pos>, u32)> = BTreeMap::new();
+    for start in 0..layout.len() {
+        search.walk(words, &layout, start, &|_| true, &mut |word, path| {
+            let path = layout.path(path);
+            let score = rule.score(word, &path);
+            match found.get_mut(word) {
+                Some(best) if best.1 >= score => {}
+                Some(best) => *best = (path, score),
+                None => {
+                    found.insert(word.to_string(), (path, score));
+                }
+            }
+        });
+    }
+    Ok(found
+        .into_iter()
+        .map(|(word, (path, score))| (word, path, score))
+        .collect())
+}
+
+// The highest scoring word in the board on its best path, or None if there are no words.
+// Ties go to the alphabetically first word.
+pub fn find_best_scoring<G: Grid>(
+    words: &impl Trie,
+    grid: &G,
+    rule: &impl ScoringRule<G::Pos>,
+) -> Result<Option<Ranked<G::Pos>>, Error> {
+    Ok(best_ties(words, grid, &Ranking::PathScore(rule))?
+        .into_iter()
+        .next())
+}
+
+// Why a word can't be played on a board
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unplayable {
+    NotAWord,
+    NotOnBoard,
+    // The rest only come up in a game::GameSession
+    TooShort,
+    AlreadyPlayed,
+    TimeUp,
+}
+
+// Every distinct path that spells word on the board, without solving the whole board
+pub fn find_paths<G: Grid>(
+    words: &impl Trie,
+    grid: &G,
+    word: &str,
+) -> Result<Vec<Vec<G::Pos>>, Error> {
+    if !words.traverse(word).is_some_and(|dict| dict.is_word()) {
+        return Err(Error::Unplayable(Unplayable::NotAWord));
+    }
+    let layout = Layout::new(grid)?;
+    let mut paths = Vec::new();
+    let mut path = Vec::new();
+    for start in 0..layout.len() {
+        find_paths_acc(&layout, start, word, &mut path, &mut paths);
+    }
+    if paths.is_empty() {
+        Err(Error::Unplayable(Unplayable::NotOnBoard))
+    } else {
+        Ok(paths)
+    }
+}
+
+// Record every way of spelling rest by extending the given path onto cell
+fn find_paths_acc<P: Copy>(
+    layout: &Layout<P>,
+    cell: usize,
+    rest: &str,
+    path: &mut Vec<usize>,
+    paths: &mut Vec<Vec<P>>,
+) {
+    let rest = match layout.tiles[cell] {
+        Tile::Letters(letters) => match rest.strip_prefix(letters.as_str()) {
+            Some(rest) => rest,
+            None => return,
+        },
+        Tile::Wildcard => match rest.chars().next() {
+            Some(c) => &rest[c.len_utf8()..],
+            None => return,
+        },
+        Tile::Blank => return,
+    };
+    path.push(cell);
+    if rest.is_empty() {
+        paths.push(layout.path(path));
+    } else {
+        for &next in layout.neighbors(cell) {
+            if !path.contains(&next) {
+                find_paths_acc(layout, next, rest, path, paths);
+            }
+        }
+    }
+    path.pop();
+}
+
+// Which letter each wildcard along the path stands for in word, or None if the path
+// doesn't spell word
+pub fn wildcard_letters<G: Grid>(
+    grid: &G,
+    word: &str,
+    path: &[G::Pos],
+) -> Option<Vec<(G::Pos, char)>> {
+    let mut rest = word;
+    let mut letters = Vec::new();
+    for pos in path {
+        match grid.tile(*pos) {
+            Tile::Letters(s) => rest = rest.strip_prefix(s.as_str())?,
+            Tile::Wildcard => {
+                let c = rest.chars().next()?;
+                letters.push((*pos, c));
+                rest = &rest[c.len_utf8()..];
+            }
+            Tile::Blank => return None,
         }
-        None => None,
+    }
+    if rest.is_empty() {
+        Some(letters)
+    } else {
+        None
     }
 }
 
-// get the neighbors of this pos
-fn neighbors(board: &Board, pos: Pos) -> Vec<Pos> {
-    let width = board[0].len();
-    let height = board.len();
-    let mut v = Vec::with_capacity(8);
+// The board flattened for searching. Cells are numbered in positions() order and
+// each cell's neighbors are looked up once, up front.
+struct Layout<'g, P> {
+    positions: Vec<P>,
+    tiles: Vec<&'g Tile>,
+    // Neighbors of cell i are neighbor_list[neighbor_start[i]..neighbor_start[i + 1]]
+    neighbor_start: Vec<usize>,
+    neighbor_list: Vec<usize>,
+}
 
-    let row = pos.0;
-    let col = pos.1;
-    if row > 0 {
-        if col > 0 {
-            v.push((row - 1, col - 1));
-            v.push((row, col - 1));
+impl<'g, P: Copy + Eq + Hash> Layout<'g, P> {
+    fn new<G: Grid<Pos = P>>(grid: &'g G) -> Result<Layout<'g, P>, Error> {
+        let positions = grid.positions();
+        if positions.len() > MAX_CELLS {
+            return Err(Error::TooManyCells {
+                cells: positions.len(),
+                limit: MAX_CELLS,
+            });
         }
-        if col + 1 < width {
-            v.push((row - 1, col + 1));
-            v.push((row, col + 1));
+        let index: HashMap<G::Pos, usize> =
+            positions.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+        let tiles = positions.iter().map(|p| grid.tile(*p)).collect();
+        let mut neighbor_start = Vec::with_capacity(positions.len() + 1);
+        let mut neighbor_list = Vec::with_capacity(positions.len() * 8);
+        for pos in &positions {
+            neighbor_start.push(neighbor_list.len());
+            neighbor_list.extend(grid.neighbors(*pos).iter().filter_map(|p| index.get(p)));
         }
-        v.push((row - 1, col));
+        neighbor_start.push(neighbor_list.len());
+        Ok(Layout {
+            positions,
+            tiles,
+            neighbor_start,
+            neighbor_list,
+        })
     }
-    if row + 1 < height {
-        if col > 0 {
-            v.push((row + 1, col - 1));
+}
+
+impl<P: Copy> Layout<'_, P> {
+    fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    fn neighbors(&self, cell: usize) -> &[usize] {
+        &self.neighbor_list[self.neighbor_start[cell]..self.neighbor_start[cell + 1]]
+    }
+
+    fn path(&self, cells: &[usize]) -> Vec<P> {
+        cells.iter().map(|&c| self.positions[c]).collect()
+    }
+}
+
+// Scratch space for walking the board, reused from one start cell to the next so
+// the search itself doesn't allocate. The explicit stack keeps long paths off the call stack.
+struct Search<'t, T> {
+    word: String,
+    path: Vec<usize>,
+    visited: Vec<u64>,
+    stack: Vec<Frame<'t, T>>,
+}
+
+// A cell on the current path
+struct Frame<'t, T> {
+    cell: usize,
+    // The dictionary after this cell's letters
+    dict: &'t T,
+    // Length of the word before this cell's letters
+    word_len: usize,
+    next_neighbor: usize,
+    // For a wildcard, every letter it can stand for; empty otherwise
+    alternatives: Vec<(char, &'t T)>,
+    next_alternative: usize,
+}
+
+impl<'t, T: Trie> Search<'t, T> {
+    fn new(cells: usize) -> Search<'t, T> {
+        Search {
+            word: String::new(),
+            path: Vec::new(),
+            visited: vec![0; cells.div_ceil(64)],
+            stack: Vec::new(),
         }
-        if col + 1 < width {
-            v.push((row + 1, col + 1));
+    }
+
+    // Call found with every word (and the cells spelling it) on a path from start,
+    // in depth first order. Paths are only extended while viable accepts the word so far.
+    fn walk<P: Copy>(
+        &mut self,
+        words: &'t T,
+        layout: &Layout<P>,
+        start: usize,
+        viable: &impl Fn(&str) -> bool,
+        found: &mut impl FnMut(&str, &[usize]),
+    ) {
+        self.enter(words, layout, start, viable, found);
+        while let Some(frame) = self.stack.last_mut() {
+            let neighbors = layout.neighbors(frame.cell);
+            if frame.next_neighbor < neighbors.len() {
+                let next = neighbors[frame.next_neighbor];
+                frame.next_neighbor += 1;
+                let dict = frame.dict;
+                if !self.is_visited(next) {
+                    self.enter(dict, layout, next, viable, found);
+                }
+            } else if frame.next_alternative < frame.alternatives.len() {
+                let (c, dict) = frame.alternatives[frame.next_alternative];
+                frame.next_alternative += 1;
+                frame.next_neighbor = 0;
+                frame.dict = dict;
+                self.word.truncate(frame.word_len);
+                self.word.push(c);
+                if !viable(&self.word) {
+                    // Try the next letter without exploring past this one
+                    frame.next_neighbor = usize::MAX;
+                } else if dict.is_word() {
+                    found(&self.word, &self.path);
+                }
+            } else {
+                let cell = frame.cell;
+                self.word.truncate(frame.word_len);
+                self.stack.pop();
+                self.path.pop();
+                self.visited[cell / 64] &= !(1 << (cell % 64));
+            }
         }
-        v.push((row + 1, col));
     }
-    v
+
+    // Step onto cell from the given dictionary node, if its tile can extend the word
+    fn enter<P: Copy>(
+        &mut self,
+        dict: &'t T,
+        layout: &Layout<P>,
+        cell: usize,
+        viable: &impl Fn(&str) -> bool,
+        found: &mut impl FnMut(&str, &[usize]),
+    ) {
+        let word_len = self.word.len();
+        let (dict, alternatives) = match layout.tiles[cell] {
+            Tile::Letters(letters) => match dict.traverse(letters) {
+                Some(next) => {
+                    self.word.push_str(letters);
+                    (next, Vec::new())
+                }
+                None => return,
+            },
+            Tile::Wildcard => {
+                let children = dict.children();
+                match children.first() {
+                    Some(&(c, next)) => {
+                        self.word.push(c);
+                        (next, children)
+                    }
+                    None => return,
+                }
+            }
+            Tile::Blank => return,
+        };
+        let explore = viable(&self.word);
+        if !explore && alternatives.is_empty() {
+            self.word.truncate(word_len);
+            return;
+        }
+        self.visited[cell / 64] |= 1 << (cell % 64);
+        self.path.push(cell);
+        self.stack.push(Frame {
+            cell,
+            dict,
+            word_len,
+            // A wildcard whose first letter is ruled out still has its other letters to try
+            next_neighbor: if explore { 0 } else { usize::MAX },
+            alternatives,
+            next_alternative: 1,
+        });
+        if explore && dict.is_word() {
+            found(&self.word, &self.path);
+        }
+    }
+
+    fn is_visited(&self, cell: usize) -> bool {
+        self.visited[cell / 64] & (1 << (cell % 64)) != 0
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::word_dict::{linkedlist::TrieLinkedList, linkedlist_typedarena::TrieLinkedListArena};
+    use crate::word_dict::{hashmap::TrieHashMap, linkedlist_typedarena::TrieLinkedListArena};
 
     use test::Bencher;
     use typed_arena::Arena;
     use super::*;
+    use crate::query::SolveQuery;
+    use crate::grid::hex::HexBoard;
+    use crate::scoring::{Boggle, WordHunt};
 
     // fn make_dict() -> impl Trie {
     //     TrieLinkedListArena::from_file("./words_alpha.txt").unwrap()
@@ -118,23 +600,345 @@ mod tests {
     fn it_works() {
     let arena = Arena::with_capacity(1027814);
     let words = TrieLinkedListArena::from_file("./words_alpha.txt", &arena).unwrap();
-        let board = vec![
-            vec!['x', 'y', 'q'],
-            vec!['h', ' ', 'o'],
-            vec!['e', 'l', 'l'],
-        ];
-        assert_eq!(find_best(&words, &board), ("hello".to_string(), vec![(1,0),(2,0),(2,1),(2,2),(1,2)]));
+        let board = Board::parse("xyq\nh o\nell").unwrap();
+        assert_eq!(find_best(&words, &board).unwrap(), ("hello".to_string(), vec![(1,0),(2,0),(2,1),(2,2),(1,2)]));
+    }
+
+    #[test]
+    fn find_all_dedups() {
+        let words = TrieHashMap::from_words(&["hell", "hello", "he", "lo", "ol", "ox", "yo"]);
+        let board = Board::parse("xyq\nh o\nell").unwrap();
+        assert_eq!(
+            find_all(&words, &board).unwrap(),
+            vec![
+                ("he".to_string(), vec![(1, 0), (2, 0)]),
+                ("hell".to_string(), vec![(1, 0), (2, 0), (2, 1), (2, 2)]),
+                ("hello".to_string(), vec![(1, 0), (2, 0), (2, 1), (2, 2), (1, 2)]),
+                ("lo".to_string(), vec![(2, 1), (1, 2)]),
+                ("ol".to_string(), vec![(1, 2), (2, 1)]),
+                ("yo".to_string(), vec![(0, 1), (1, 2)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn score_board() {
+        let words = TrieHashMap::from_words(&["hell", "hello", "he", "lo", "ol", "ox", "yo"]);
+        let board = Board::parse("xyq\nh o\nell").unwrap();
+        assert_eq!(total_score(&words, &board, &Boggle).unwrap(), 3);
+        assert_eq!(total_score(&words, &board, &WordHunt).unwrap(), 1200);
+        let ranked = rank_by_score(&words, &board, &Boggle).unwrap();
+        assert_eq!(
+            ranked
+                .iter()
+                .map(|(word, _, points)| (word.as_str(), *points))
+                .collect::<Vec<_>>(),
+            vec![("hello", 2), ("hell", 1)]
+        );
+    }
+
+    #[test]
+    fn top_row() {
+        let words = TrieHashMap::from_words(&["cat", "tag"]);
+        let board = Board::parse("cat\nxxg").unwrap();
+        assert_eq!(
+            find_all(&words, &board).unwrap(),
+            vec![
+                ("cat".to_string(), vec![(0, 0), (0, 1), (0, 2)]),
+                ("tag".to_string(), vec![(0, 2), (0, 1), (1, 2)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn orthogonal_only() {
+        let words = TrieHashMap::from_words(&["cat", "tax"]);
+        let board = Board::parse("cx\nta").unwrap();
+        assert_eq!(find_all(&words, &board).unwrap().len(), 2);
+        assert_eq!(
+            find_all_with(&words, &board, &Adjacency::orthogonal()).unwrap(),
+            vec![("tax".to_string(), vec![(1, 0), (1, 1), (0, 1)])]
+        );
+    }
+
+    #[test]
+    fn wrap_around() {
+        let words = TrieHashMap::from_words(&["tab"]);
+        let board = Board::parse("axxt\nxxxb").unwrap();
+        assert_eq!(find_all(&words, &board).unwrap(), vec![]);
+        let torus = Adjacency::standard().wrapping();
+        assert_eq!(
+            find_all_with(&words, &board, &torus).unwrap(),
+            vec![("tab".to_string(), vec![(0, 3), (0, 0), (1, 3)])]
+        );
+        assert_eq!(
+            find_best_with(&words, &board, &torus).unwrap(),
+            ("tab".to_string(), vec![(0, 3), (0, 0), (1, 3)])
+        );
+    }
+
+    #[test]
+    fn hex_board() {
+        let words = TrieHashMap::from_words(&["bed", "beg", "dab", "fed"]);
+        // a b c
+        //  d e f
+        // g h i
+        let board = HexBoard::parse("abc\n def\nghi").unwrap();
+        assert_eq!(
+            find_all(&words, &board).unwrap(),
+            vec![
+                ("bed".to_string(), vec![(1, 0), (1, 1), (0, 1)]),
+                ("dab".to_string(), vec![(0, 1), (0, 0), (1, 0)]),
+                ("fed".to_string(), vec![(2, 1), (1, 1), (0, 1)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn multi_letter_tiles() {
+        let words = TrieHashMap::from_words(&["quit", "quilt", "thin", "tin"]);
+        let board = Board::parse("[qu]il\n[th]tn").unwrap();
+        assert_eq!(
+            find_all(&words, &board).unwrap(),
+            vec![
+                ("quilt".to_string(), vec![(0, 0), (0, 1), (0, 2), (1, 1)]),
+                ("quit".to_string(), vec![(0, 0), (0, 1), (1, 1)]),
+                ("thin".to_string(), vec![(1, 0), (0, 1), (1, 2)]),
+                ("tin".to_string(), vec![(1, 1), (0, 1), (1, 2)]),
+            ]
+        );
+        assert_eq!(
+            find_best(&words, &board).unwrap(),
+            ("quilt".to_string(), vec![(0, 0), (0, 1), (0, 2), (1, 1)])
+        );
+        // quilt covers 4 cells but scores as 5 letters
+        assert_eq!(total_score(&words, &board, &Boggle).unwrap(), 5);
+    }
+
+    #[test]
+    fn wildcards() {
+        let words = TrieHashMap::from_words(&["cab", "cat", "cot", "act"]);
+        let board = Board::parse("c?\n-t").unwrap();
+        assert_eq!(
+            find_all(&words, &board).unwrap(),
+            vec![
+                ("act".to_string(), vec![(0, 1), (0, 0), (1, 1)]),
+                ("cat".to_string(), vec![(0, 0), (0, 1), (1, 1)]),
+                ("cot".to_string(), vec![(0, 0), (0, 1), (1, 1)]),
+            ]
+        );
+        assert_eq!(
+            wildcard_letters(&board, "cot", &[(0, 0), (0, 1), (1, 1)]),
+            Some(vec![((0, 1), 'o')])
+        );
+        assert_eq!(wildcard_letters(&board, "cab", &[(0, 0), (0, 1), (1, 1)]), None);
+        assert_eq!(wildcard_letters(&board, "cots", &[(0, 0), (0, 1), (1, 1)]), None);
+        assert_eq!(wildcard_letters(&board, "co", &[(0, 0), (0, 1), (1, 1)]), None);
+        let board = Board::parse("[qu]?\n??").unwrap();
+        let words = TrieHashMap::from_words(&["quit", "quilt"]);
+        let (word, path) = find_best(&words, &board).unwrap();
+        assert_eq!(word, "quilt");
+        assert_eq!(
+            wildcard_letters(&board, &word, &path),
+            Some(vec![(path[1], 'i'), (path[2], 'l'), (path[3], 't')])
+        );
+    }
+
+    #[test]
+    fn parallel_matches_serial() {
+        let words = TrieHashMap::from_words(&[
+            "he", "hell", "hello", "lo", "ol", "yo", "ole", "hole", "helo",
+        ]);
+        let board = Board::parse("xyq\nh o\nell").unwrap();
+        for threads in [1, 2, 3, 16] {
+            assert_eq!(
+                find_all_parallel(&words, &board, threads).unwrap(),
+                find_all(&words, &board).unwrap()
+            );
+            assert_eq!(
+                find_best_parallel(&words, &board, threads).unwrap(),
+                find_best(&words, &board).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn long_paths() {
+        // A single path through every cell of a long board
+        let long = "a".repeat(1000);
+        let words = TrieHashMap::from_words(&[&long]);
+        let board = Board::parse(&long).unwrap();
+        let (word, path) = find_best(&words, &board).unwrap();
+        assert_eq!(word, long);
+        assert_eq!(path.len(), 1000);
+    }
+
+    #[test]
+    fn filtered_solve() {
+        let words = TrieHashMap::from_words(&[
+            "he", "hell", "hello", "lo", "ol", "yo", "ole", "hole", "helo",
+        ]);
+        let board = Board::parse("xyq\nh o\nell").unwrap();
+        let found = |query: &SolveQuery| -> Vec<String> {
+            find_matching(&words, &board, query).unwrap()
+                .into_iter()
+                .map(|(word, _)| word)
+                .collect()
+        };
+        assert_eq!(found(&SolveQuery::new().min_len(4)), vec!["hell", "hello", "helo"]);
+        assert_eq!(found(&SolveQuery::new().max_len(2)), vec!["he", "lo", "ol", "yo"]);
+        assert_eq!(
+            found(&SolveQuery::new().requiring("o").max_len(3)),
+            vec!["lo", "ol", "ole", "yo"]
+        );
+        assert_eq!(found(&SolveQuery::new().forbidding("o")), vec!["he", "hell"]);
+        assert_eq!(found(&SolveQuery::new().matching("h?l*")), vec!["hell", "hello", "helo"]);
+        assert_eq!(
+            find_matching(&words, &board, &SolveQuery::new()).unwrap(),
+            find_all(&words, &board).unwrap()
+        );
+    }
+
+    #[test]
+    fn filtered_wildcards() {
+        let words = TrieHashMap::from_words(&["cab", "cat", "cot", "act"]);
+        let board = Board::parse("c?\n-t").unwrap();
+        let query = SolveQuery::new().matching("c?t");
+        assert_eq!(
+            find_matching(&words, &board, &query).unwrap(),
+            vec![
+                ("cat".to_string(), vec![(0, 0), (0, 1), (1, 1)]),
+                ("cot".to_string(), vec![(0, 0), (0, 1), (1, 1)]),
+            ]
+        );
+        let query = SolveQuery::new().forbidding("a");
+        assert_eq!(
+            find_matching(&words, &board, &query).unwrap(),
+            vec![("cot".to_string(), vec![(0, 0), (0, 1), (1, 1)])]
+        );
+    }
+
+    #[test]
+    fn paths_for_word() {
+        let words = TrieHashMap::from_words(&["hell", "hello", "lo", "ox"]);
+        let board = Board::parse("xyq\nh o\nell").unwrap();
+        assert_eq!(
+            find_paths(&words, &board, "hello").unwrap(),
+            vec![vec![(1, 0), (2, 0), (2, 1), (2, 2), (1, 2)]]
+        );
+        assert_eq!(
+            find_paths(&words, &board, "lo").unwrap(),
+            vec![vec![(2, 1), (1, 2)], vec![(2, 2), (1, 2)]]
+        );
+        assert!(matches!(
+            find_paths(&words, &board, "ox"),
+            Err(Error::Unplayable(Unplayable::NotOnBoard))
+        ));
+        assert!(matches!(
+            find_paths(&words, &board, "yell"),
+            Err(Error::Unplayable(Unplayable::NotAWord))
+        ));
+        assert!(matches!(
+            find_paths(&words, &board, "hel"),
+            Err(Error::Unplayable(Unplayable::NotAWord))
+        ));
+        assert!(matches!(
+            find_paths(&words, &board, ""),
+            Err(Error::Unplayable(Unplayable::NotAWord))
+        ));
+    }
+
+    #[test]
+    fn paths_through_special_tiles() {
+        let words = TrieHashMap::from_words(&["quit", "quilt"]);
+        let board = Board::parse("[qu]?\n?t").unwrap();
+        assert_eq!(
+            find_paths(&words, &board, "quit").unwrap(),
+            vec![
+                vec![(0, 0), (0, 1), (1, 0)],
+                vec![(0, 0), (0, 1), (1, 1)],
+                vec![(0, 0), (1, 0), (0, 1)],
+                vec![(0, 0), (1, 0), (1, 1)],
+            ]
+        );
+        assert_eq!(
+            find_paths(&words, &board, "quilt").unwrap(),
+            vec![
+                vec![(0, 0), (0, 1), (1, 0), (1, 1)],
+                vec![(0, 0), (1, 0), (0, 1), (1, 1)],
+            ]
+        );
+    }
+
+    #[test]
+    fn used_cells() {
+        let words = TrieHashMap::from_words(&["ab"]);
+        // The first path for "ab" starts top left, the second bottom right
+        let board = Board::parse("ab\nxa").unwrap();
+        let (found, used) = find_all_and_used_cells(&words, &board).unwrap();
+        assert_eq!(found, find_all(&words, &board).unwrap());
+        assert_eq!(used, vec![(0, 0), (0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn path_counts() {
+        let words = TrieHashMap::from_words(&["he", "hell", "lo", "ole"]);
+        let board = Board::parse("xyq\nh o\nell").unwrap();
+        let counts: Vec<(String, usize)> = count_paths(&words, &board, 100).unwrap()
+            .into_iter()
+            .map(|(word, _, count)| (word, count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("he".to_string(), 1),
+                ("hell".to_string(), 1),
+                ("lo".to_string(), 2),
+                ("ole".to_string(), 1),
+            ]
+        );
+
+        let words = TrieHashMap::from_words(&["aaaa"]);
+        let board = Board::parse("aaa\naaa\naaa").unwrap();
+        let count = |cap| count_paths(&words, &board, cap).unwrap()[0].2;
+        assert_eq!(count(1_000_000), 496);
+        assert_eq!(count(50), 50);
+    }
+
+    #[test]
+    fn best_scoring_paths() {
+        use crate::scoring::Ruzzle;
+
+        let words = TrieHashMap::from_words(&["ab", "bx"]);
+        let board = Board::parse("aa\nbx").unwrap();
+        let ruzzle = Ruzzle::with_modifiers(&board, "DW TL\n.  .").unwrap();
+        // The first path found for "ab" starts on the double word, but the triple letter path
+        // would win without it
+        assert_eq!(
+            find_all_best_paths(&words, &board, &ruzzle).unwrap(),
+            vec![
+                ("ab".to_string(), vec![(0, 0), (1, 0)], 8),
+                ("bx".to_string(), vec![(1, 0), (1, 1)], 11),
+            ]
+        );
+        let ruzzle = Ruzzle::with_modifiers(&board, ". TL\n. .").unwrap();
+        assert_eq!(
+            find_all_best_paths(&words, &board, &ruzzle).unwrap()[0],
+            ("ab".to_string(), vec![(0, 1), (1, 0)], 6)
+        );
+        assert_eq!(
+            find_best_scoring(&words, &board, &ruzzle).unwrap(),
+            Some(("bx".to_string(), vec![(1, 0), (1, 1)], 11))
+        );
+        let words = TrieHashMap::from_words(&["zz"]);
+        assert_eq!(find_best_scoring(&words, &board, &ruzzle).unwrap(), None);
     }
 
     #[bench]
     fn bench_solver(b: &mut Bencher) {
     let arena = Arena::with_capacity(1027814);
     let words = TrieLinkedListArena::from_file("./words_alpha.txt", &arena).unwrap();
-        let board = vec![
-            vec!['x', 'y', 'q'],
-            vec!['h', ' ', 'o'],
-            vec!['e', 'l', 'l'],
-        ];
-        b.iter(||find_best(&words, &board));
+        let board = Board::parse("xyq\nh o\nell").unwrap();
+        b.iter(||find_best(&words, &board).unwrap());
     }
 }
@@ -1,8 +1,7 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, Error};
-
-use super::Trie;
+use super::{load_words, Skipped, Trie};
+use crate::error::Error;
+use crate::normalize::Normalizer;
 
 #[derive(Clone, Debug)]
 pub struct TrieHashMap {
@@ -19,15 +18,22 @@ impl TrieHashMap {
     }
 
     pub fn from_file(filename: &str) -> Result<TrieHashMap, Error> {
+        Ok(TrieHashMap::load(filename, &Normalizer::new())?.0)
+    }
+
+    // Load a dictionary normalized the given way, along with the lines that were left out
+    pub fn load(
+        filename: &str,
+        normalizer: &Normalizer,
+    ) -> Result<(TrieHashMap, Vec<Skipped>), Error> {
         let mut dict = TrieHashMap::blank();
-        let file = File::open(filename)?;
-        let lines = std::io::BufReader::new(file).lines();
-        for word in lines.flatten() {
-            dict.add_word(&word);
-        }
-        Ok(dict)
+        let skipped = load_words(filename, normalizer, |word| {
+            dict.add_word(word);
+            Ok(())
+        })?;
+        Ok((dict, skipped))
     }
-    fn add_word(&mut self, word: &str) {
+    pub fn add_word(&mut self, word: &str) {
         let mut cursor = self;
         for c in word.chars() {
             if !cursor.next.contains_key(&c) {
@@ -40,6 +46,18 @@ impl TrieHashMap {
     }
 }
 
+#[cfg(test)]
+impl TrieHashMap {
+    // A dictionary of just these words
+    pub fn from_words(words: &[&str]) -> TrieHashMap {
+        let mut dict = TrieHashMap::blank();
+        for word in words {
+            dict.add_word(word);
+        }
+        dict
+    }
+}
+
 impl Trie for TrieHashMap {
     fn traverse(&self, word: &str) -> Option<&TrieHashMap> {
         let mut cursor = self;
@@ -57,6 +75,13 @@ impl Trie for TrieHashMap {
     fn is_word(&self) -> bool {
         self.is_word
     }
+
+    fn children(&self) -> Vec<(char, &TrieHashMap)> {
+        let mut children: Vec<(char, &TrieHashMap)> =
+            self.next.iter().map(|(c, t)| (*c, t)).collect();
+        children.sort_by_key(|(c, _)| *c);
+        children
+    }
 }
 
 #[cfg(test)]
@@ -74,6 +99,8 @@ mod tests {
         assert_eq!(dict.traverse("hello").unwrap().is_word(), true);
         assert_eq!(dict.traverse("he").unwrap().is_word(), false);
         assert_eq!(dict.traverse("fjidso").is_none(), true);
+        let letters: Vec<char> = dict.children().iter().map(|(c, _)| *c).collect();
+        assert_eq!(letters, vec!['a', 'h']);
     }
 
     #[test]
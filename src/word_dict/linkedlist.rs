@@ -1,8 +1,8 @@
 use std::fmt::Debug;
-use std::fs::File;
-use std::io::{BufRead, Error};
 
-use super::Trie;
+use super::{load_words, Skipped, Trie};
+use crate::error::Error;
+use crate::normalize::Normalizer;
 
 #[derive(Clone)]
 pub struct TrieLinkedList {
@@ -42,13 +42,20 @@ impl TrieLinkedList {
     }
 
     pub fn from_file(filename: &str) -> Result<TrieLinkedList, Error> {
+        Ok(TrieLinkedList::load(filename, &Normalizer::new())?.0)
+    }
+
+    // Load a dictionary normalized the given way, along with the lines that were left out
+    pub fn load(
+        filename: &str,
+        normalizer: &Normalizer,
+    ) -> Result<(TrieLinkedList, Vec<Skipped>), Error> {
         let mut dict = TrieLinkedList::blank(' ');
-        let file = File::open(filename)?;
-        let lines = std::io::BufReader::new(file).lines();
-        for word in lines.flatten() {
-            dict.add_word(&word);
-        }
-        Ok(dict)
+        let skipped = load_words(filename, normalizer, |word| {
+            dict.add_word(word);
+            Ok(())
+        })?;
+        Ok((dict, skipped))
     }
     fn add_word(&mut self, word: &str) {
         let mut cursor = self;
@@ -125,6 +132,16 @@ impl Trie for TrieLinkedList {
     fn is_word(&self) -> bool {
         self.is_word
     }
+
+    fn children(&self) -> Vec<(char, &TrieLinkedList)> {
+        let mut children = Vec::new();
+        let mut cursor = self.child.as_deref();
+        while let Some(child) = cursor {
+            children.push((child.letter, child));
+            cursor = child.sibling.as_deref();
+        }
+        children
+    }
 }
 
 #[cfg(test)]
@@ -148,6 +165,8 @@ mod tests {
         assert_eq!(dict.traverse("hello").unwrap().is_word(), true);
         assert_eq!(dict.traverse("he").unwrap().is_word(), false);
         assert_eq!(dict.traverse("fjidso").is_none(), true);
+        let letters: Vec<char> = dict.children().iter().map(|(c, _)| *c).collect();
+        assert_eq!(letters, vec!['a', 'h', 's']);
         assert_eq!(dict.traverse("spoonlike").unwrap().is_word(), true);
         assert_eq!(dict.traverse("spoonmaker").unwrap().is_word(), true);
         assert_eq!(dict.traverse("spoonmaking").unwrap().is_word(), true);
@@ -1,11 +1,11 @@
 use std::cell::Cell;
 use std::fmt::Debug;
-use std::fs::File;
-use std::io::{BufRead, Error};
 
 use typed_arena::Arena;
 
-use super::Trie;
+use super::{load_words, Skipped, Trie};
+use crate::error::Error;
+use crate::normalize::Normalizer;
 
 #[derive(Clone)]
 pub struct TrieLinkedListArena<'a> {
@@ -48,13 +48,21 @@ impl<'a> TrieLinkedListArena<'a> {
         filename: &str,
         arena: &'a Arena<TrieLinkedListArena<'a>>,
     ) -> Result<TrieLinkedListArena<'a>, Error> {
+        Ok(TrieLinkedListArena::load(filename, arena, &Normalizer::new())?.0)
+    }
+
+    // Load a dictionary normalized the given way, along with the lines that were left out
+    pub fn load(
+        filename: &str,
+        arena: &'a Arena<TrieLinkedListArena<'a>>,
+        normalizer: &Normalizer,
+    ) -> Result<(TrieLinkedListArena<'a>, Vec<Skipped>), Error> {
         let dict = TrieLinkedListArena::blank(' ');
-        let file = File::open(filename)?;
-        let lines = std::io::BufReader::new(file).lines();
-        for word in lines.flatten() {
-            dict.add_word(&arena, &word);
-        }
-        Ok(dict)
+        let skipped = load_words(filename, normalizer, |word| {
+            dict.add_word(arena, word);
+            Ok(())
+        })?;
+        Ok((dict, skipped))
     }
 }
 
@@ -144,6 +152,16 @@ impl<'a> Trie for TrieLinkedListArena<'a> {
     fn is_word(&self) -> bool {
         self.is_word
     }
+
+    fn children(&self) -> Vec<(char, &TrieLinkedListArena<'a>)> {
+        let mut children = Vec::new();
+        let mut cursor = self.child.get();
+        while let Some(child) = cursor {
+            children.push((child.letter, child));
+            cursor = child.sibling.get();
+        }
+        children
+    }
 }
 
 #[cfg(test)]
@@ -168,6 +186,8 @@ mod tests {
         assert_eq!(dict.traverse("hello").unwrap().is_word(), true);
         assert_eq!(dict.traverse("he").unwrap().is_word(), false);
         assert_eq!(dict.traverse("fjidso").is_none(), true);
+        let letters: Vec<char> = dict.children().iter().map(|(c, _)| *c).collect();
+        assert_eq!(letters, vec!['a', 'h', 's']);
         assert_eq!(dict.traverse("spoonlike").unwrap().is_word(), true);
         assert_eq!(dict.traverse("spoonmaker").unwrap().is_word(), true);
         assert_eq!(dict.traverse("spoonmaking").unwrap().is_word(), true);
@@ -6,8 +6,140 @@ pub mod naive;
 pub mod radix_lib;
 pub mod yada;
 
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use crate::error::Error;
+use crate::normalize::Normalizer;
+
 pub trait Trie {
     // fn add_word(&mut self, word: &str);
     fn traverse(&self, word: &str) -> Option<&Self>;
     fn is_word(&self) -> bool;
+    // Every letter that can follow this prefix, in alphabetical order
+    fn children(&self) -> Vec<(char, &Self)>;
+}
+
+// A dictionary line that was left out while loading. reason is Error::MalformedLine or
+// Error::UnsupportedChar.
+#[derive(Debug)]
+pub struct Skipped {
+    // Counting from 1
+    pub line: usize,
+    pub text: String,
+    pub reason: Error,
+}
+
+// Call add with every word in a dictionary file, one word per line, after normalizing it.
+// Blank lines are ignored. Lines that aren't a single word, and words add rejects with
+// Error::UnsupportedChar, are skipped and returned; only failing to read the file is an error.
+pub fn load_words(
+    filename: &str,
+    normalizer: &Normalizer,
+    mut add: impl FnMut(&str) -> Result<(), Error>,
+) -> Result<Vec<Skipped>, Error> {
+    let io_error = |source| Error::Io {
+        path: filename.to_string(),
+        source,
+    };
+    let file = File::open(filename).map_err(io_error)?;
+    let mut reader = BufReader::new(file);
+    let mut skipped = Vec::new();
+    let mut bytes = Vec::new();
+    let mut line = 0;
+    loop {
+        bytes.clear();
+        if reader.read_until(b'\n', &mut bytes).map_err(io_error)? == 0 {
+            break;
+        }
+        line += 1;
+        let mut skip = |text: &str, reason| {
+            skipped.push(Skipped {
+                line,
+                text: text.to_string(),
+                reason,
+            })
+        };
+        let malformed = || Error::MalformedLine {
+            path: filename.to_string(),
+            line,
+        };
+        let text = match std::str::from_utf8(&bytes) {
+            Ok(text) => normalizer.line(text),
+            Err(_) => {
+                let text = String::from_utf8_lossy(&bytes);
+                skip(normalizer.line(&text), malformed());
+                continue;
+            }
+        };
+        if text.is_empty() {
+            continue;
+        }
+        if text.contains(char::is_whitespace) {
+            skip(text, malformed());
+            continue;
+        }
+        match add(&normalizer.word(text)) {
+            Ok(()) => {}
+            Err(e @ Error::UnsupportedChar { .. }) => skip(text, e),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(
+        name: &str,
+        contents: &[u8],
+        normalizer: &Normalizer,
+    ) -> Result<(Vec<String>, Vec<Skipped>), Error> {
+        let path = std::env::temp_dir().join(format!("boggle-{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        let mut words = Vec::new();
+        let result = load_words(path.to_str().unwrap(), normalizer, |w| {
+            if w.contains('!') {
+                return Err(Error::UnsupportedChar {
+                    word: w.to_string(),
+                    found: '!',
+                });
+            }
+            words.push(w.to_string());
+            Ok(())
+        });
+        std::fs::remove_file(&path).unwrap();
+        result.map(|skipped| (words, skipped))
+    }
+
+    #[test]
+    fn loading() {
+        let norm = Normalizer::new();
+        let (words, skipped) = load("crlf", b"Cat\r\n\r\n dog \nno!\n", &norm).unwrap();
+        assert_eq!(words, vec!["cat", "dog"]);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!((skipped[0].line, skipped[0].text.as_str()), (4, "no!"));
+        assert!(matches!(
+            skipped[0].reason,
+            Error::UnsupportedChar { found: '!', .. }
+        ));
+
+        let (words, skipped) = load("bad", b"cat\nhot dog\n\xff\xfe\nCaf\xc3\xa9", &norm).unwrap();
+        assert_eq!(words, vec!["cat", "café"]);
+        let lines: Vec<usize> = skipped.iter().map(|s| s.line).collect();
+        assert_eq!(lines, vec![2, 3]);
+        assert!(skipped
+            .iter()
+            .all(|s| matches!(s.reason, Error::MalformedLine { .. })));
+
+        let (words, _) = load("accents", "Café\nÉcole".as_bytes(), &norm.fold_accents()).unwrap();
+        assert_eq!(words, vec!["cafe", "ecole"]);
+
+        assert!(matches!(
+            load_words("./no-such-dictionary.txt", &norm, |_| Ok(())),
+            Err(Error::Io { .. })
+        ));
+    }
 }
@@ -1,7 +1,8 @@
 // Not even a trie. Just a sorted list we go through
 
-use std::fs::File;
-use std::io::{BufRead, Error};
+use super::load_words;
+use crate::error::Error;
+use crate::normalize::Normalizer;
 
 #[derive(Clone, Debug)]
 pub struct TrieNaive {
@@ -15,11 +16,10 @@ impl TrieNaive {
 
     pub fn from_file(filename: &str) -> Result<TrieNaive, Error> {
         let mut dict = TrieNaive::blank();
-        let file = File::open(filename)?;
-        let lines = std::io::BufReader::new(file).lines();
-        for word in lines.flatten() {
-            dict.add_word(&word);
-        }
+        load_words(filename, &Normalizer::new(), |word| {
+            dict.add_word(word);
+            Ok(())
+        })?;
         dict.vec.sort_unstable();
         Ok(dict)
     }
@@ -1,8 +1,8 @@
 use std::fmt::Debug;
-use std::fs::File;
-use std::io::{BufRead, Error};
 
-use super::Trie;
+use super::{load_words, Trie};
+use crate::error::Error;
+use crate::normalize::Normalizer;
 
 #[derive(Debug)]
 pub struct TrieRadix {
@@ -18,11 +18,10 @@ impl TrieRadix {
 
     pub fn from_file(filename: &str) -> Result<TrieRadix, Error> {
         let mut dict = TrieRadix::blank(' ');
-        let file = File::open(filename)?;
-        let lines = std::io::BufReader::new(file).lines();
-        for word in lines.flatten() {
-            dict.add_word(&word);
-        }
+        load_words(filename, &Normalizer::new(), |word| {
+            dict.add_word(word);
+            Ok(())
+        })?;
         Ok(dict)
     }
     fn add_word(&mut self, word: &str) {
@@ -1,62 +1,127 @@
+use std::marker::PhantomData;
 
-use std::fs::File;
-use std::io::{BufRead, Error};
+use super::{load_words, Skipped, Trie};
+use crate::error::Error;
+use crate::normalize::Normalizer;
 
-use super::Trie;
+// The letters an array-indexed trie has room for. Each node holds one slot per letter, so
+// smaller alphabets make smaller tries.
+pub trait Alphabet {
+    // Every letter, sorted by code point. A letter's index is its position here.
+    const LETTERS: &'static [char];
+
+    fn index(c: char) -> Option<usize> {
+        Self::LETTERS.binary_search(&c).ok()
+    }
+}
+
+// Plain a-z. Also covers Dutch, where "ij" is played as two letters.
+#[derive(Clone, Copy, Debug)]
+pub struct English;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Spanish;
+
+#[derive(Clone, Copy, Debug)]
+pub struct German;
+
+#[derive(Clone, Copy, Debug)]
+pub struct French;
+
+const A_TO_Z: [char; 26] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
+    't', 'u', 'v', 'w', 'x', 'y', 'z',
+];
+
+impl Alphabet for English {
+    const LETTERS: &'static [char] = &A_TO_Z;
+
+    fn index(c: char) -> Option<usize> {
+        match c {
+            'a'..='z' => Some((c as usize) - ('a' as usize)),
+            _ => None,
+        }
+    }
+}
+
+impl Alphabet for Spanish {
+    const LETTERS: &'static [char] = &[
+        'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r',
+        's', 't', 'u', 'v', 'w', 'x', 'y', 'z', 'á', 'é', 'í', 'ñ', 'ó', 'ú', 'ü',
+    ];
+}
+
+impl Alphabet for German {
+    const LETTERS: &'static [char] = &[
+        'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r',
+        's', 't', 'u', 'v', 'w', 'x', 'y', 'z', 'ß', 'ä', 'ö', 'ü',
+    ];
+}
+
+impl Alphabet for French {
+    const LETTERS: &'static [char] = &[
+        'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r',
+        's', 't', 'u', 'v', 'w', 'x', 'y', 'z', 'à', 'â', 'æ', 'ç', 'è', 'é', 'ê', 'ë', 'î', 'ï',
+        'ô', 'ù', 'û', 'ü', 'ÿ', 'œ',
+    ];
+}
 
 #[derive(Clone, Debug)]
-pub struct TrieVec {
-    next: Box<WordDict>,
+pub struct TrieVec<A: Alphabet = English> {
+    next: Box<WordDict<A>>,
     is_word: bool,
+    alphabet: PhantomData<A>,
 }
 
-pub type WordDict = [Option<TrieVec>; 26];
+// One slot per letter of the alphabet
+pub type WordDict<A> = [Option<TrieVec<A>>];
 
-impl TrieVec {
-    pub fn blank() -> TrieVec {
+impl<A: Alphabet> TrieVec<A> {
+    pub fn blank() -> TrieVec<A> {
         TrieVec {
-            next: Box::new([
-                None, None, None, None, None, None, None, None, None, None, None, None, None, None,
-                None, None, None, None, None, None, None, None, None, None, None, None,
-            ]),
+            next: A::LETTERS.iter().map(|_| None).collect(),
             is_word: false,
+            alphabet: PhantomData,
         }
     }
 
-    pub fn from_file(filename: &str) -> Result<TrieVec, Error> {
+    pub fn from_file(filename: &str) -> Result<TrieVec<A>, Error> {
+        Ok(TrieVec::load(filename, &Normalizer::new())?.0)
+    }
+
+    // Load a dictionary normalized the given way, along with the lines that were left out
+    pub fn load(
+        filename: &str,
+        normalizer: &Normalizer,
+    ) -> Result<(TrieVec<A>, Vec<Skipped>), Error> {
         let mut dict = TrieVec::blank();
-        let file = File::open(filename)?;
-        let lines = std::io::BufReader::new(file).lines();
-        for word in lines.flatten() {
-            dict.add_word(&word);
-        }
-        Ok(dict)
+        let skipped = load_words(filename, normalizer, |word| dict.add_word(word))?;
+        Ok((dict, skipped))
     }
-    fn add_word(&mut self, word: &str) {
+
+    // Only letters of the alphabet fit in the table; anything else is rejected before the
+    // word is touched
+    pub fn add_word(&mut self, word: &str) -> Result<(), Error> {
+        if let Some(found) = word.chars().find(|c| A::index(*c).is_none()) {
+            return Err(Error::UnsupportedChar {
+                word: word.to_string(),
+                found,
+            });
+        }
         let mut cursor = self;
         for c in word.chars() {
-            if cursor.next[char_to_index(c)].is_none() {
-                cursor.next[char_to_index(c)] = Some(TrieVec::blank());
-            }
-            let entry = cursor.next.as_mut()[char_to_index(c)]
-                .as_mut()
-                .expect("failed to build word dict");
-            cursor = entry;
+            cursor = cursor.next[A::index(c).unwrap()].get_or_insert_with(TrieVec::blank);
         }
         cursor.is_word = true;
+        Ok(())
     }
 
 }
-impl Trie for TrieVec {
-    fn traverse(&self, word: &str) -> Option<&TrieVec> {
+impl<A: Alphabet> Trie for TrieVec<A> {
+    fn traverse(&self, word: &str) -> Option<&TrieVec<A>> {
         let mut cursor = self;
         for c in word.chars() {
-            match cursor.next[char_to_index(c)].as_ref() {
-                None => return None,
-                Some(words) => {
-                    cursor = words;
-                }
-            }
+            cursor = A::index(c).and_then(|i| cursor.next[i].as_ref())?;
         }
         Some(cursor)
     }
@@ -64,11 +129,14 @@ impl Trie for TrieVec {
     fn is_word(&self) -> bool {
         self.is_word
     }
-}
-
 
-fn char_to_index(c: char) -> usize {
-    (c as usize) - ('a' as usize)
+    fn children(&self) -> Vec<(char, &TrieVec<A>)> {
+        self.next
+            .iter()
+            .enumerate()
+            .filter_map(|(i, t)| Some((A::LETTERS[i], t.as_ref()?)))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -78,19 +146,80 @@ mod tests {
 
     #[test]
     fn simple_test() {
-        let mut dict = TrieVec::blank();
-        dict.add_word("hell");
-        dict.add_word("abc");
-        dict.add_word("hello");
+        let mut dict: TrieVec = TrieVec::blank();
+        dict.add_word("hell").unwrap();
+        dict.add_word("abc").unwrap();
+        dict.add_word("hello").unwrap();
         assert_eq!(dict.traverse("abc").unwrap().is_word(), true);
         assert_eq!(dict.traverse("hello").unwrap().is_word(), true);
         assert_eq!(dict.traverse("he").unwrap().is_word(), false);
         assert_eq!(dict.traverse("fjidso").is_none(), true);
+        assert!(matches!(
+            dict.add_word("café"),
+            Err(Error::UnsupportedChar { found: 'é', .. })
+        ));
+        assert!(dict.traverse("caf").is_none());
+        assert!(dict.traverse("Hell").is_none());
+        let letters: Vec<char> = dict.children().iter().map(|(c, _)| *c).collect();
+        assert_eq!(letters, vec!['a', 'h']);
+    }
+
+    #[test]
+    fn normalized_load() {
+        let path = std::env::temp_dir().join(format!("boggle-vec-{}", std::process::id()));
+        std::fs::write(&path, "Hello\r\nCafé\r\nWORLD\r\n").unwrap();
+        let filename = path.to_str().unwrap();
+        let (dict, skipped) = TrieVec::<English>::load(filename, &Normalizer::new()).unwrap();
+        assert!(dict.traverse("hello").unwrap().is_word());
+        assert!(dict.traverse("world").unwrap().is_word());
+        assert_eq!((skipped[0].line, skipped[0].text.as_str()), (2, "Café"));
+        let (dict, skipped) =
+            TrieVec::<English>::load(filename, &Normalizer::new().fold_accents()).unwrap();
+        assert!(dict.traverse("cafe").unwrap().is_word());
+        assert!(skipped.is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn alphabets() {
+        for letters in [
+            English::LETTERS,
+            Spanish::LETTERS,
+            German::LETTERS,
+            French::LETTERS,
+        ] {
+            assert!(letters.windows(2).all(|w| w[0] < w[1]));
+        }
+        for (i, c) in A_TO_Z.iter().enumerate() {
+            assert_eq!(English::index(*c), Some(i));
+        }
+        assert_eq!(English::index('ñ'), None);
+        assert_eq!(Spanish::index('ñ'), Some(29));
+        assert_eq!(French::LETTERS.len(), 42);
+
+        let mut dict: TrieVec<Spanish> = TrieVec::blank();
+        dict.add_word("año").unwrap();
+        dict.add_word("ano").unwrap();
+        assert!(dict.traverse("año").unwrap().is_word());
+        let after_a = dict.traverse("a").unwrap().children();
+        let letters: Vec<char> = after_a.iter().map(|(c, _)| *c).collect();
+        assert_eq!(letters, vec!['n', 'ñ']);
+        assert!(matches!(
+            dict.add_word("straße"),
+            Err(Error::UnsupportedChar { found: 'ß', .. })
+        ));
+
+        let mut dict: TrieVec<German> = TrieVec::blank();
+        dict.add_word("straße").unwrap();
+        dict.add_word("über").unwrap();
+        assert!(dict.traverse("straße").unwrap().is_word());
+        assert!(dict.traverse("über").unwrap().is_word());
+        assert!(dict.traverse("año").is_none());
     }
 
     #[test]
     fn dict_size() {
-        let dict = TrieVec::from_file("./words_alpha.txt").unwrap();
+        let dict: TrieVec = TrieVec::from_file("./words_alpha.txt").unwrap();
         let mut count = 0;
         let mut stack = Vec::new();
         stack.push(&dict);
@@ -104,7 +233,7 @@ mod tests {
 
     #[bench]
     fn bench_build_dict(b: &mut Bencher) {
-        b.iter(|| TrieVec::from_file("./words_alpha.txt").unwrap())
+        b.iter(|| TrieVec::<English>::from_file("./words_alpha.txt").unwrap())
     }
 
 }
\ No newline at end of file
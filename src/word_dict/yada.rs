@@ -1,11 +1,11 @@
 use std::fmt::Debug;
-use std::fs::File;
-use std::io::{BufRead, Error};
 
 use yada::builder::DoubleArrayBuilder;
 use yada::DoubleArray;
 
-use super::Trie;
+use super::{load_words, Trie};
+use crate::error::Error;
+use crate::normalize::Normalizer;
 
 pub struct TrieYada {
     trie: yada::DoubleArray<Vec<u8>>,
@@ -13,9 +13,12 @@ pub struct TrieYada {
 
 impl TrieYada {
     pub fn from_file(filename: &str) -> Result<TrieYada, Error> {
-        let file = File::open(filename)?;
-        let lines = std::io::BufReader::new(file).lines();
-        Ok(TrieYada::from_words(lines.flatten().collect()))
+        let mut words = Vec::new();
+        load_words(filename, &Normalizer::new(), |word| {
+            words.push(word.to_string());
+            Ok(())
+        })?;
+        Ok(TrieYada::from_words(words))
     }
     fn from_words(words: Vec<String>) -> TrieYada {
         let mut vec = words.clone();
@@ -0,0 +1,239 @@
+use crate::board::{Board, Pos, Tile};
+use crate::normalize::Normalizer;
+use crate::word_dict::hashmap::TrieHashMap;
+use crate::word_dict::Trie;
+
+// One of the eight straight lines a word search word can run along
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    // (row, col) change for one step
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+            Direction::Right => (0, 1),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (1, 0),
+            Direction::DownLeft => (1, -1),
+            Direction::Left => (0, -1),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    // The cell steps cells away from start, if it's still on the board
+    fn step(self, board: &Board, start: Pos, steps: usize) -> Option<Pos> {
+        let (dr, dc) = self.offset();
+        let row = start.0.checked_add_signed(dr * steps as isize)?;
+        let col = start.1.checked_add_signed(dc * steps as isize)?;
+        (row < board.height() && col < board.width()).then_some((row, col))
+    }
+}
+
+// A word found in a straight line. length counts cells, which is fewer than the word's
+// letters when it crosses a multi-letter tile.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Placement {
+    pub word: String,
+    pub start: Pos,
+    pub direction: Direction,
+    pub length: usize,
+}
+
+impl Placement {
+    // The cells spelling the word, in order
+    pub fn cells(&self) -> Vec<Pos> {
+        let (dr, dc) = self.direction.offset();
+        (0..self.length as isize)
+            .map(|i| {
+                (
+                    self.start.0.wrapping_add_signed(dr * i),
+                    self.start.1.wrapping_add_signed(dc * i),
+                )
+            })
+            .collect()
+    }
+}
+
+// Every word laid out in a straight line on the board, word search style, sorted by word,
+// then start cell, then direction. A word appears once per place it can be read, and a
+// single cell has no direction so words have to cover at least two cells. Blanks end a line;
+// wildcards stand for any letter.
+pub fn find_straight(words: &impl Trie, board: &Board) -> Vec<Placement> {
+    let mut found = Vec::new();
+    let mut word = String::new();
+    for row in 0..board.height() {
+        for col in 0..board.width() {
+            for direction in Direction::ALL {
+                let line = Line {
+                    board,
+                    start: (row, col),
+                    direction,
+                };
+                line.search(words, 0, &mut word, &mut found);
+            }
+        }
+    }
+    found.sort_by(|a, b| (&a.word, a.start, a.direction).cmp(&(&b.word, b.start, b.direction)));
+    found
+}
+
+// Like find_straight, but only looking for the given words, e.g. a puzzle's word list.
+// The words are normalized the same way a dictionary is.
+pub fn find_listed(list: &[&str], board: &Board) -> Vec<Placement> {
+    let normalizer = Normalizer::new();
+    let mut words = TrieHashMap::blank();
+    for word in list {
+        let word = normalizer.word(normalizer.line(word));
+        if !word.is_empty() {
+            words.add_word(&word);
+        }
+    }
+    find_straight(&words, board)
+}
+
+// A line running from start to the edge of the board
+struct Line<'b> {
+    board: &'b Board,
+    start: Pos,
+    direction: Direction,
+}
+
+impl Line<'_> {
+    // Extend word, which covers the first cells of the line, by the next cell
+    fn search<T: Trie>(
+        &self,
+        dict: &T,
+        cells: usize,
+        word: &mut String,
+        found: &mut Vec<Placement>,
+    ) {
+        let Some(pos) = self.direction.step(self.board, self.start, cells) else {
+            return;
+        };
+        let len = word.len();
+        match &self.board.rows()[pos.0][pos.1] {
+            Tile::Letters(letters) => {
+                if let Some(next) = dict.traverse(letters) {
+                    word.push_str(letters);
+                    self.found(next, cells + 1, word, found);
+                }
+            }
+            Tile::Wildcard => {
+                for (c, next) in dict.children() {
+                    word.push(c);
+                    self.found(next, cells + 1, word, found);
+                    word.truncate(len);
+                }
+            }
+            Tile::Blank => {}
+        }
+        word.truncate(len);
+    }
+
+    // Record word if it's complete, then keep going
+    fn found<T: Trie>(
+        &self,
+        dict: &T,
+        cells: usize,
+        word: &mut String,
+        found: &mut Vec<Placement>,
+    ) {
+        if cells >= 2 && dict.is_word() {
+            found.push(Placement {
+                word: word.clone(),
+                start: self.start,
+                direction: self.direction,
+                length: cells,
+            });
+        }
+        self.search(dict, cells, word, found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placed(word: &str, start: Pos, direction: Direction, length: usize) -> Placement {
+        Placement {
+            word: word.to_string(),
+            start,
+            direction,
+            length,
+        }
+    }
+
+    #[test]
+    fn straight_lines() {
+        use Direction::*;
+        let words = TrieHashMap::from_words(&["cat", "dog", "god", "ox", "to", "tod", "coo"]);
+        let board = Board::parse("cat\nxox\ndog").unwrap();
+        assert_eq!(
+            find_straight(&words, &board),
+            vec![
+                placed("cat", (0, 0), Right, 3),
+                placed("dog", (2, 0), Right, 3),
+                placed("god", (2, 2), Left, 3),
+                placed("ox", (1, 1), Right, 2),
+                placed("ox", (1, 1), Left, 2),
+                placed("ox", (2, 1), UpRight, 2),
+                placed("ox", (2, 1), UpLeft, 2),
+                placed("to", (0, 2), DownLeft, 2),
+                placed("tod", (0, 2), DownLeft, 3),
+            ]
+        );
+        assert_eq!(
+            placed("tod", (0, 2), DownLeft, 3).cells(),
+            vec![(0, 2), (1, 1), (2, 0)]
+        );
+    }
+
+    #[test]
+    fn special_tiles() {
+        let words = TrieHashMap::from_words(&["cat", "cot", "quit", "a"]);
+        let board = Board::parse("c?t\n[qu]it\na-a").unwrap();
+        let found: Vec<(String, usize)> = find_straight(&words, &board)
+            .into_iter()
+            .map(|p| (p.word, p.length))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("cat".to_string(), 3),
+                ("cot".to_string(), 3),
+                ("quit".to_string(), 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn word_list() {
+        let board = Board::parse("cat\nxox\ndog").unwrap();
+        assert_eq!(
+            find_listed(&["CAT ", "bird", ""], &board),
+            vec![placed("cat", (0, 0), Direction::Right, 3)]
+        );
+    }
+}